@@ -0,0 +1,31 @@
+/// Compare the work done by each solving method on the bundled puzzles.
+/// Run with `cargo run --release --example compare`.
+use sudoku::{boards, Method, Sudoku};
+
+fn main() {
+    let puzzles = [
+        ("VALID_PUZZLE_1", boards::VALID_PUZZLE_1),
+        ("VALID_PUZZLE_2", boards::VALID_PUZZLE_2),
+        ("VALID_PUZZLE_3", boards::VALID_PUZZLE_3),
+    ];
+
+    for (name, initial) in puzzles.iter() {
+        for method in Method::all().iter() {
+            let mut puzzle = Sudoku::new(initial.to_vec()).unwrap();
+            let outcome = method.solver().solve(&mut puzzle);
+            let stats = outcome.stats;
+            println!(
+                "{} {:>17}: {:?}, {} nodes, depth {}, {} placements, {} guesses, {} backtracks, {:?}",
+                name,
+                format!("{:?}", method),
+                outcome.result,
+                stats.nodes,
+                stats.max_depth,
+                stats.placements,
+                stats.guesses,
+                stats.backtracks,
+                stats.elapsed
+            );
+        }
+    }
+}
@@ -1,4 +1,4 @@
-/// Pre-defined sudoku boards meant for unit tests.
+//! Pre-defined sudoku boards for examples and unit tests.
 
 pub const VALID_PUZZLE_1: [(usize, usize, u8); 22] = [
     (0, 1, 1),
@@ -243,6 +243,78 @@ pub const INVALID_SOLUTION: [(usize, usize, u8); 81] = [
     (8, 8, 8),
 ];
 
+/// `VALID_SOLUTION` with every third cell cleared, so that singles alone solve it.
+pub const EASY_PUZZLE: [(usize, usize, u8); 54] = [
+    (0, 1, 2),
+    (0, 2, 3),
+    (0, 4, 5),
+    (0, 5, 6),
+    (0, 7, 8),
+    (0, 8, 9),
+    (1, 0, 7),
+    (1, 1, 8),
+    (1, 3, 1),
+    (1, 4, 2),
+    (1, 6, 4),
+    (1, 7, 5),
+    (2, 0, 4),
+    (2, 2, 6),
+    (2, 3, 7),
+    (2, 5, 9),
+    (2, 6, 1),
+    (2, 8, 3),
+    (3, 1, 3),
+    (3, 2, 1),
+    (3, 4, 6),
+    (3, 5, 4),
+    (3, 7, 9),
+    (3, 8, 7),
+    (4, 0, 8),
+    (4, 1, 9),
+    (4, 3, 2),
+    (4, 4, 3),
+    (4, 6, 5),
+    (4, 7, 6),
+    (5, 0, 5),
+    (5, 2, 4),
+    (5, 3, 8),
+    (5, 5, 7),
+    (5, 6, 2),
+    (5, 8, 1),
+    (6, 1, 1),
+    (6, 2, 2),
+    (6, 4, 4),
+    (6, 5, 5),
+    (6, 7, 7),
+    (6, 8, 8),
+    (7, 0, 9),
+    (7, 1, 7),
+    (7, 3, 3),
+    (7, 4, 1),
+    (7, 6, 6),
+    (7, 7, 4),
+    (8, 0, 6),
+    (8, 2, 5),
+    (8, 3, 9),
+    (8, 5, 8),
+    (8, 6, 3),
+    (8, 8, 2),
+];
+
+/// A puzzle without conflicts that has no solution: the last cell of the first row
+/// can only hold 9, which its column already holds.
+pub const UNSOLVABLE_PUZZLE: [(usize, usize, u8); 9] = [
+    (0, 0, 1),
+    (0, 1, 2),
+    (0, 2, 3),
+    (0, 3, 4),
+    (0, 4, 5),
+    (0, 5, 6),
+    (0, 6, 7),
+    (0, 7, 8),
+    (1, 8, 9),
+];
+
 pub const VALID_ROW: [(usize, usize, u8); 9] = [
     (4, 0, 1),
     (4, 1, 2),
@@ -0,0 +1,109 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Limits on how much work a solver may do before giving up.
+/// A budget with no limits set lets the search run to completion.
+#[derive(Clone, Debug, Default)]
+pub struct Budget {
+    max_nodes: Option<u64>,
+    deadline: Option<Instant>,
+    cancel: Option<Arc<AtomicBool>>,
+}
+
+impl Budget {
+    /// Return a budget without any limits.
+    pub fn unlimited() -> Self {
+        Self::default()
+    }
+
+    /// Stop after visiting the given number of search nodes.
+    pub fn max_nodes(mut self, max_nodes: u64) -> Self {
+        self.max_nodes = Some(max_nodes);
+        self
+    }
+
+    /// Stop once the given instant has passed.
+    pub fn deadline(mut self, deadline: Instant) -> Self {
+        self.deadline = Some(deadline);
+        self
+    }
+
+    /// Stop once the given duration has elapsed, counting from now.
+    pub fn timeout(self, timeout: Duration) -> Self {
+        self.deadline(Instant::now() + timeout)
+    }
+
+    /// Stop as soon as the given flag is set, for example from another thread.
+    pub fn cancel_flag(mut self, cancel: Arc<AtomicBool>) -> Self {
+        self.cancel = Some(cancel);
+        self
+    }
+
+    /// Return true iff a search that has visited `nodes` nodes must stop.
+    pub(crate) fn exhausted_(&self, nodes: u64) -> bool {
+        if let Some(max_nodes) = self.max_nodes {
+            if nodes >= max_nodes {
+                return true;
+            }
+        }
+        if let Some(cancel) = &self.cancel {
+            if cancel.load(Ordering::Relaxed) {
+                return true;
+            }
+        }
+        if let Some(deadline) = self.deadline {
+            if Instant::now() >= deadline {
+                return true;
+            }
+        }
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{boards, Sudoku, SudokuError};
+
+    #[test]
+    fn unlimited_budget() {
+        let budget = Budget::unlimited();
+        assert!(!budget.exhausted_(u64::MAX));
+    }
+
+    #[test]
+    fn node_budget() {
+        let budget = Budget::unlimited().max_nodes(5);
+        assert!(!budget.exhausted_(4));
+        assert!(budget.exhausted_(5));
+    }
+
+    #[test]
+    fn cancelled_solve() {
+        let cancel = Arc::new(AtomicBool::new(true));
+        let budget = Budget::unlimited().cancel_flag(cancel.clone());
+        let mut puzzle = Sudoku::new(boards::VALID_PUZZLE_1.to_vec()).unwrap();
+        let original = puzzle.clone();
+        assert_eq!(
+            puzzle.solve_within(&budget),
+            Err(SudokuError::BudgetExceeded)
+        );
+        assert_eq!(puzzle, original);
+
+        cancel.store(false, Ordering::Relaxed);
+        assert!(puzzle.solve_within(&budget).is_ok());
+        assert!(puzzle.verify());
+    }
+
+    #[test]
+    fn expired_deadline() {
+        let budget = Budget::unlimited().deadline(Instant::now());
+        let mut puzzle = Sudoku::new(Vec::new()).unwrap();
+        assert_eq!(
+            puzzle.solve_within(&budget),
+            Err(SudokuError::BudgetExceeded)
+        );
+        assert_eq!(puzzle, Sudoku::new(Vec::new()).unwrap());
+    }
+}
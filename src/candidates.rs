@@ -0,0 +1,266 @@
+use crate::{Digits, Sudoku, SudokuError, Unit, CAGE_COLS, CAGE_ROWS, COLS, ROWS};
+
+/// Pencil marks for a sudoku board: the set of values still considered possible in
+/// each cell. Filled cells have no candidates.
+/// Unlike `Sudoku::candidates`, which is recomputed from the board on every call,
+/// eliminations made here are kept until they are restored.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Candidates {
+    cells: [[Digits; COLS]; ROWS],
+    // Bit `row * COLS + col` is set iff that cell holds a value.
+    filled: u128,
+}
+
+impl Candidates {
+    /// Return the candidates of every cell on the given board, allowing each value that
+    /// does not already appear in the cell's row, column or cage.
+    pub fn new(sudoku: &Sudoku) -> Self {
+        let mut cells = [[Digits::none(); COLS]; ROWS];
+        let mut filled = (1 << (ROWS * COLS)) - 1;
+        for (row, col) in sudoku.open_cells() {
+            cells[row][col] = sudoku.candidates(row, col);
+            filled &= !(1 << (row * COLS + col));
+        }
+        Self { cells, filled }
+    }
+
+    /// Return the candidates of the given cell.
+    /// Panics if the row or column index is out of bounds.
+    pub fn get(&self, row: usize, col: usize) -> Digits {
+        self.cells[row][col]
+    }
+
+    /// Return true iff the given value is a candidate of the given cell.
+    pub fn contains(&self, row: usize, col: usize, val: u8) -> bool {
+        self.cells[row][col].contains(val)
+    }
+
+    /// Remove the given value from the candidates of the given cell.
+    /// Return true iff it was a candidate, or an error if the position or value is out of
+    /// range. A filled cell has no candidates to remove.
+    pub fn eliminate(&mut self, row: usize, col: usize, val: u8) -> Result<bool, SudokuError> {
+        Self::check_(row, col, val)?;
+        let present = self.contains(row, col, val);
+        self.eliminate_(row, col, val);
+        Ok(present)
+    }
+
+    /// Remove the given value from the candidates of the given cell, which must lie on
+    /// the board.
+    pub(crate) fn eliminate_(&mut self, row: usize, col: usize, val: u8) {
+        self.cells[row][col].remove(val);
+    }
+
+    /// Add the given value back to the candidates of the given cell.
+    /// Return true iff it was not already a candidate, or an error if the position or
+    /// value is out of range or if the cell holds a value.
+    pub fn restore(&mut self, row: usize, col: usize, val: u8) -> Result<bool, SudokuError> {
+        Self::check_(row, col, val)?;
+        if self.filled & (1 << (row * COLS + col)) != 0 {
+            return Err(SudokuError::FilledCell { row, col });
+        }
+        let absent = !self.contains(row, col, val);
+        self.cells[row][col].insert(val);
+        Ok(absent)
+    }
+
+    /// Return an error unless the given cell lies on the board and the value is between
+    /// 1 and 9.
+    fn check_(row: usize, col: usize, val: u8) -> Result<(), SudokuError> {
+        if row >= ROWS || col >= COLS {
+            return Err(SudokuError::OutOfBounds { row, col, val });
+        }
+        if val == 0 || val > 9 {
+            return Err(SudokuError::InvalidValue { row, col, val });
+        }
+        Ok(())
+    }
+
+    /// Update the candidates for the given value being placed in the given cell.
+    /// The cell loses all of its candidates, and the value is eliminated from every other
+    /// cell in the same row, column or cage.
+    /// Return an error if the position or value is out of range or if the cell already
+    /// holds a value.
+    pub fn place(&mut self, row: usize, col: usize, val: u8) -> Result<(), SudokuError> {
+        Self::check_(row, col, val)?;
+        if self.filled & (1 << (row * COLS + col)) != 0 {
+            return Err(SudokuError::FilledCell { row, col });
+        }
+        self.place_(row, col, val);
+        Ok(())
+    }
+
+    /// Update the candidates for the given value being placed in the given cell like
+    /// `place`, without checking the position, value or cell.
+    pub(crate) fn place_(&mut self, row: usize, col: usize, val: u8) {
+        self.cells[row][col] = Digits::none();
+        self.filled |= 1 << (row * COLS + col);
+        let units = [
+            Unit::Row(row),
+            Unit::Col(col),
+            Unit::Cage(row / CAGE_ROWS, col / CAGE_COLS),
+        ];
+        for &unit in &units {
+            for &(r, c) in unit.cells().iter() {
+                self.cells[r][c].remove(val);
+            }
+        }
+    }
+
+    /// Return an iterator over the row and column indexes of the cells in the given unit
+    /// that still allow the given value, in the order of `Unit::cells`.
+    pub fn cells_with(&self, unit: Unit, val: u8) -> impl Iterator<Item = (usize, usize)> + '_ {
+        let cells = unit.cells();
+        (0..cells.len())
+            .map(move |i| cells[i])
+            .filter(move |&(row, col)| self.contains(row, col, val))
+    }
+
+    /// Return the union of the candidates of every cell in the given unit.
+    pub fn unit_digits(&self, unit: Unit) -> Digits {
+        unit.cells()
+            .iter()
+            .fold(Digits::none(), |digits, &(row, col)| {
+                digits | self.cells[row][col]
+            })
+    }
+
+    /// Return the total number of candidates on the board.
+    pub fn len(&self) -> usize {
+        self.cells.iter().flatten().map(|digits| digits.len()).sum()
+    }
+
+    /// Return true iff no cell has any candidates.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl From<&Sudoku> for Candidates {
+    fn from(sudoku: &Sudoku) -> Self {
+        Candidates::new(sudoku)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::boards;
+
+    #[test]
+    fn initial_candidates() {
+        let puzzle = Sudoku::new(boards::VALID_PUZZLE_1.to_vec()).unwrap();
+        let candidates = Candidates::new(&puzzle);
+        for (row, col, _) in puzzle.cells() {
+            assert_eq!(candidates.get(row, col), puzzle.candidates(row, col));
+        }
+        assert!(candidates.get(0, 1).is_empty());
+        assert!(!candidates.contains(0, 0, 1));
+        assert!(candidates.contains(0, 0, 2));
+
+        let solution = Sudoku::new(boards::VALID_SOLUTION.to_vec()).unwrap();
+        assert!(Candidates::from(&solution).is_empty());
+    }
+
+    #[test]
+    fn eliminate_and_restore() {
+        let puzzle = Sudoku::new(boards::VALID_PUZZLE_1.to_vec()).unwrap();
+        let mut candidates = Candidates::new(&puzzle);
+        let total = candidates.len();
+        assert_eq!(candidates.eliminate(0, 0, 2), Ok(true));
+        assert_eq!(candidates.eliminate(0, 0, 2), Ok(false));
+        assert!(!candidates.contains(0, 0, 2));
+        assert_eq!(candidates.len(), total - 1);
+        assert_eq!(candidates.restore(0, 0, 2), Ok(true));
+        assert_eq!(candidates.restore(0, 0, 2), Ok(false));
+        assert_eq!(candidates, Candidates::new(&puzzle));
+    }
+
+    #[test]
+    fn eliminate_and_restore_errors() {
+        let puzzle = Sudoku::new(boards::VALID_PUZZLE_1.to_vec()).unwrap();
+        let mut candidates = Candidates::new(&puzzle);
+        let original = candidates.clone();
+        assert_eq!(
+            candidates.eliminate(0, 0, 200),
+            Err(SudokuError::InvalidValue {
+                row: 0,
+                col: 0,
+                val: 200
+            })
+        );
+        assert_eq!(
+            candidates.restore(0, 9, 2),
+            Err(SudokuError::OutOfBounds {
+                row: 0,
+                col: 9,
+                val: 2
+            })
+        );
+        assert_eq!(
+            candidates.restore(0, 1, 2),
+            Err(SudokuError::FilledCell { row: 0, col: 1 })
+        );
+        assert_eq!(candidates.eliminate(0, 1, 1), Ok(false));
+        candidates.place(0, 0, 2).unwrap();
+        assert!(candidates.restore(0, 0, 2).is_err());
+        assert_eq!(candidates.eliminate(0, 0, 2), Ok(false));
+        assert_ne!(candidates, original);
+    }
+
+    #[test]
+    fn place_errors() {
+        let puzzle = Sudoku::new(boards::VALID_PUZZLE_1.to_vec()).unwrap();
+        let mut candidates = Candidates::new(&puzzle);
+        let original = candidates.clone();
+        assert_eq!(
+            candidates.place(0, 1, 7),
+            Err(SudokuError::FilledCell { row: 0, col: 1 })
+        );
+        assert_eq!(
+            candidates.place(0, 0, 0),
+            Err(SudokuError::InvalidValue {
+                row: 0,
+                col: 0,
+                val: 0
+            })
+        );
+        assert_eq!(
+            candidates.place(9, 0, 2),
+            Err(SudokuError::OutOfBounds {
+                row: 9,
+                col: 0,
+                val: 2
+            })
+        );
+        assert_eq!(candidates, original);
+        candidates.place(0, 0, 2).unwrap();
+        assert_eq!(
+            candidates.place(0, 0, 6),
+            Err(SudokuError::FilledCell { row: 0, col: 0 })
+        );
+    }
+
+    #[test]
+    fn place_eliminates_from_peers() {
+        let mut puzzle = Sudoku::new(boards::VALID_PUZZLE_1.to_vec()).unwrap();
+        let mut candidates = Candidates::new(&puzzle);
+        candidates.place(0, 0, 2).unwrap();
+        puzzle.set(0, 0, 2).unwrap();
+        assert_eq!(candidates, Candidates::new(&puzzle));
+    }
+
+    #[test]
+    fn cells_with_value() {
+        let puzzle = Sudoku::new(boards::VALID_PUZZLE_1.to_vec()).unwrap();
+        let mut candidates = Candidates::new(&puzzle);
+        let cells: Vec<_> = candidates.cells_with(Unit::Row(0), 2).collect();
+        assert_eq!(cells, vec![(0, 0), (0, 4), (0, 5)]);
+        assert_eq!(candidates.cells_with(Unit::Row(0), 1).count(), 0);
+        assert!(!candidates.unit_digits(Unit::Row(0)).contains(1));
+
+        candidates.eliminate(1, 1, 2).unwrap();
+        let cells: Vec<_> = candidates.cells_with(Unit::Cage(0, 0), 2).collect();
+        assert_eq!(cells, vec![(0, 0)]);
+    }
+}
@@ -0,0 +1,194 @@
+use std::fmt::{self, Debug, Formatter};
+use std::iter::FromIterator;
+use std::ops::{BitAnd, BitOr, Not, Sub};
+
+/// A set of sudoku digits between 1 and 9, stored as a 9-bit mask.
+/// Bit `v` is set iff digit `v` is in the set.
+#[derive(Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Digits(u16);
+
+impl Digits {
+    const MASK: u16 = 0b11_1111_1110;
+
+    /// Return the empty set.
+    pub fn none() -> Self {
+        Digits(0)
+    }
+
+    /// Return the set of all digits from 1 to 9.
+    pub fn all() -> Self {
+        Digits(Self::MASK)
+    }
+
+    /// Return the set containing only the given digit.
+    /// Values outside of 1 to 9 give the empty set.
+    pub fn single(val: u8) -> Self {
+        if val == 0 || val > 9 {
+            return Digits::none();
+        }
+        Digits(1 << val)
+    }
+
+    /// Return true iff the given digit is in the set.
+    pub fn contains(self, val: u8) -> bool {
+        val < 16 && self.0 & (1 << val) != 0
+    }
+
+    /// Add the given digit to the set. Values outside of 1 to 9 are ignored.
+    pub fn insert(&mut self, val: u8) {
+        *self = *self | Self::single(val);
+    }
+
+    /// Remove the given digit from the set.
+    pub fn remove(&mut self, val: u8) {
+        *self = *self - Self::single(val);
+    }
+
+    /// Return the number of digits in the set.
+    pub fn len(self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    /// Return true iff the set contains no digits.
+    pub fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    /// Return the smallest digit in the set, if any.
+    pub fn first(self) -> Option<u8> {
+        if self.is_empty() {
+            None
+        } else {
+            Some(self.0.trailing_zeros() as u8)
+        }
+    }
+
+    /// Return an iterator over the digits in the set in increasing order.
+    pub fn iter(self) -> DigitsIter {
+        DigitsIter(self)
+    }
+}
+
+impl BitOr for Digits {
+    type Output = Digits;
+
+    fn bitor(self, rhs: Digits) -> Digits {
+        Digits(self.0 | rhs.0)
+    }
+}
+
+impl BitAnd for Digits {
+    type Output = Digits;
+
+    fn bitand(self, rhs: Digits) -> Digits {
+        Digits(self.0 & rhs.0)
+    }
+}
+
+impl Sub for Digits {
+    type Output = Digits;
+
+    fn sub(self, rhs: Digits) -> Digits {
+        Digits(self.0 & !rhs.0)
+    }
+}
+
+impl Not for Digits {
+    type Output = Digits;
+
+    fn not(self) -> Digits {
+        Digits(!self.0 & Self::MASK)
+    }
+}
+
+impl FromIterator<u8> for Digits {
+    fn from_iter<I: IntoIterator<Item = u8>>(iter: I) -> Self {
+        let mut digits = Digits::none();
+        for val in iter {
+            digits.insert(val);
+        }
+        digits
+    }
+}
+
+impl IntoIterator for Digits {
+    type Item = u8;
+    type IntoIter = DigitsIter;
+
+    fn into_iter(self) -> DigitsIter {
+        self.iter()
+    }
+}
+
+impl Debug for Digits {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+/// Iterator over the digits of a `Digits` set, created by `Digits::iter`.
+pub struct DigitsIter(Digits);
+
+impl Iterator for DigitsIter {
+    type Item = u8;
+
+    fn next(&mut self) -> Option<u8> {
+        let val = self.0.first()?;
+        self.0.remove(val);
+        Some(val)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.0.len(), Some(self.0.len()))
+    }
+}
+
+impl ExactSizeIterator for DigitsIter {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn insert_and_remove() {
+        let mut digits = Digits::none();
+        assert!(digits.is_empty());
+        digits.insert(3);
+        digits.insert(9);
+        assert!(digits.contains(3));
+        assert!(!digits.contains(4));
+        assert_eq!(digits.len(), 2);
+        digits.remove(3);
+        assert_eq!(digits, Digits::single(9));
+    }
+
+    #[test]
+    fn set_operations() {
+        let a: Digits = vec![1, 2, 3].into_iter().collect();
+        let b: Digits = vec![3, 4].into_iter().collect();
+        assert_eq!((a | b).iter().collect::<Vec<_>>(), vec![1, 2, 3, 4]);
+        assert_eq!(a & b, Digits::single(3));
+        assert_eq!((a - b).iter().collect::<Vec<_>>(), vec![1, 2]);
+        assert_eq!((!a).len(), 6);
+        assert_eq!(Digits::all().len(), 9);
+        assert_eq!(Digits::single(0), Digits::none());
+    }
+
+    #[test]
+    fn out_of_range_values() {
+        let mut digits = Digits::all();
+        for &val in &[0, 10, 15, 16, 200, 255] {
+            assert_eq!(Digits::single(val), Digits::none());
+            assert!(!digits.contains(val));
+            digits.insert(val);
+            digits.remove(val);
+        }
+        assert_eq!(digits, Digits::all());
+    }
+
+    #[test]
+    fn debug_format() {
+        let digits: Digits = vec![5, 2].into_iter().collect();
+        assert_eq!(format!("{:?}", digits), "{2, 5}");
+    }
+}
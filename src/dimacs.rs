@@ -0,0 +1,236 @@
+use crate::{Sudoku, SudokuError, Unit, COLS, ROWS};
+
+/// Number of boolean variables in the encoding, one per (row, column, value) triple.
+const VARIABLES: usize = ROWS * COLS * 9;
+
+/// Return the DIMACS variable that is true iff `val` is placed at (`row`, `col`).
+/// Variables are numbered from 1.
+fn variable(row: usize, col: usize, val: u8) -> usize {
+    (row * COLS + col) * 9 + val as usize
+}
+
+impl Sudoku {
+    /// Return the board encoded as a CNF formula in DIMACS format.
+    /// Every cell holds at least one and at most one value, no value repeats within a
+    /// row, column or cage, and each filled cell is a unit clause.
+    pub fn to_dimacs(&self) -> String {
+        let mut clauses: Vec<Vec<isize>> = Vec::new();
+        for row in 0..ROWS {
+            for col in 0..COLS {
+                clauses.push(
+                    (1..10)
+                        .map(|val| variable(row, col, val) as isize)
+                        .collect(),
+                );
+                for a in 1..10 {
+                    for b in a + 1..10 {
+                        clauses.push(vec![
+                            -(variable(row, col, a) as isize),
+                            -(variable(row, col, b) as isize),
+                        ]);
+                    }
+                }
+            }
+        }
+        for unit in Unit::all() {
+            let cells = unit.cells();
+            for val in 1..10 {
+                for (i, &(r1, c1)) in cells.iter().enumerate() {
+                    for &(r2, c2) in &cells[i + 1..] {
+                        clauses.push(vec![
+                            -(variable(r1, c1, val) as isize),
+                            -(variable(r2, c2, val) as isize),
+                        ]);
+                    }
+                }
+            }
+        }
+        for (row, col, val) in self.cells() {
+            if let Some(val) = val {
+                clauses.push(vec![variable(row, col, val) as isize]);
+            }
+        }
+
+        let mut cnf = format!(
+            "c sudoku: variable (row * 81 + col * 9 + val) is true iff val is at (row, col)\n\
+             p cnf {} {}\n",
+            VARIABLES,
+            clauses.len()
+        );
+        for clause in clauses {
+            for literal in clause {
+                cnf.push_str(&literal.to_string());
+                cnf.push(' ');
+            }
+            cnf.push_str("0\n");
+        }
+        cnf
+    }
+
+    /// Fill the board from a DIMACS model produced by a SAT solver for `to_dimacs`.
+    /// Comment lines and 'v' prefixes are skipped, and every positive literal places its
+    /// value. If the solver reported the formula as unsatisfiable, return
+    /// `SudokuError::Unsolvable`. A model that repeats a value within a unit or leaves a
+    /// cell empty does not satisfy the encoding, and is rejected with
+    /// `SudokuError::ConflictingPlacement` or `SudokuError::IncompleteModel` respectively.
+    /// On error the board is left unchanged.
+    pub fn apply_dimacs_model(&mut self, model: &str) -> Result<(), SudokuError> {
+        let mut sudoku = self.clone();
+        for line in model.lines() {
+            let line = line.trim();
+            if line.starts_with('c') {
+                continue;
+            }
+            if line.contains("UNSAT") {
+                return Err(SudokuError::Unsolvable);
+            }
+            if line.starts_with('s') || line == "SAT" {
+                continue;
+            }
+            let literals = line.strip_prefix('v').unwrap_or(line);
+            for token in literals.split_whitespace() {
+                let literal: isize = token.parse().map_err(|_| SudokuError::InvalidModel {
+                    token: token.to_string(),
+                })?;
+                if literal.unsigned_abs() > VARIABLES {
+                    return Err(SudokuError::InvalidModel {
+                        token: token.to_string(),
+                    });
+                }
+                if literal <= 0 {
+                    continue;
+                }
+                let index = literal as usize - 1;
+                let (row, col, val) = (index / 9 / COLS, index / 9 % COLS, (index % 9) as u8 + 1);
+                match sudoku.cell(row, col) {
+                    Some(v) if v == val => {}
+                    Some(_) => return Err(SudokuError::DuplicatePlacement { row, col, val }),
+                    None => sudoku.place_(row, col, val),
+                }
+            }
+        }
+        if let Some(&conflict) = sudoku.conflicts().first() {
+            return Err(SudokuError::ConflictingPlacement(conflict));
+        }
+        if let Some((row, col)) = sudoku.open_cells().next() {
+            return Err(SudokuError::IncompleteModel { row, col });
+        }
+        *self = sudoku;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{boards, Conflict, Unit};
+
+    /// Return a DIMACS model assigning every variable according to the given full board.
+    fn model_of(solution: &Sudoku) -> String {
+        let mut model = String::from("s SATISFIABLE\nv");
+        for row in 0..ROWS {
+            for col in 0..COLS {
+                for val in 1..10 {
+                    let var = variable(row, col, val) as isize;
+                    let literal = if solution.cell(row, col) == Some(val) {
+                        var
+                    } else {
+                        -var
+                    };
+                    model.push_str(&format!(" {}", literal));
+                }
+            }
+        }
+        model.push_str(" 0\n");
+        model
+    }
+
+    #[test]
+    fn encode_header() {
+        let puzzle = Sudoku::new(boards::VALID_PUZZLE_1.to_vec()).unwrap();
+        let cnf = puzzle.to_dimacs();
+        let header = cnf.lines().find(|line| line.starts_with('p')).unwrap();
+        assert_eq!(header, format!("p cnf 729 {}", 81 + 4 * 81 * 36 + 22));
+        let clauses = cnf.lines().filter(|line| line.ends_with(" 0")).count();
+        assert_eq!(clauses, 81 + 4 * 81 * 36 + 22);
+        assert!(cnf
+            .lines()
+            .any(|line| line == format!("{} 0", variable(0, 1, 1))));
+    }
+
+    #[test]
+    fn apply_model() {
+        let puzzle = Sudoku::new(boards::VALID_PUZZLE_1.to_vec()).unwrap();
+        let mut solution = puzzle.clone();
+        solution.solve().unwrap();
+        let mut applied = puzzle.clone();
+        applied.apply_dimacs_model(&model_of(&solution)).unwrap();
+        assert_eq!(applied, solution);
+    }
+
+    #[test]
+    fn apply_unsatisfiable_model() {
+        let mut puzzle = Sudoku::new(Vec::new()).unwrap();
+        assert_eq!(
+            puzzle.apply_dimacs_model("s UNSATISFIABLE\n"),
+            Err(SudokuError::Unsolvable)
+        );
+    }
+
+    #[test]
+    fn apply_invalid_model() {
+        let mut puzzle = Sudoku::new(boards::VALID_PUZZLE_1.to_vec()).unwrap();
+        let original = puzzle.clone();
+        assert_eq!(
+            puzzle.apply_dimacs_model("v 1 x 0"),
+            Err(SudokuError::InvalidModel {
+                token: "x".to_string()
+            })
+        );
+        assert_eq!(
+            puzzle.apply_dimacs_model("v 730 0"),
+            Err(SudokuError::InvalidModel {
+                token: "730".to_string()
+            })
+        );
+        assert_eq!(
+            puzzle.apply_dimacs_model(&format!("v 1 {} 0", variable(0, 1, 2))),
+            Err(SudokuError::DuplicatePlacement {
+                row: 0,
+                col: 1,
+                val: 2
+            })
+        );
+        assert_eq!(puzzle, original);
+    }
+
+    #[test]
+    fn apply_conflicting_model() {
+        let mut puzzle = Sudoku::new(Vec::new()).unwrap();
+        assert_eq!(
+            puzzle.apply_dimacs_model("v 5 14 0"),
+            Err(SudokuError::ConflictingPlacement(Conflict {
+                unit: Unit::Row(0),
+                first: (0, 0),
+                second: (0, 1),
+                val: 5,
+            }))
+        );
+        assert_eq!(puzzle, Sudoku::new(Vec::new()).unwrap());
+    }
+
+    #[test]
+    fn apply_partial_model() {
+        let puzzle = Sudoku::new(boards::VALID_PUZZLE_1.to_vec()).unwrap();
+        let mut solution = puzzle.clone();
+        solution.solve().unwrap();
+        let mut partial = solution.clone();
+        partial.remove_(8, 8);
+        let mut applied = puzzle.clone();
+        assert_eq!(
+            applied.apply_dimacs_model(&model_of(&partial)),
+            Err(SudokuError::IncompleteModel { row: 8, col: 8 })
+        );
+        assert_eq!(applied, puzzle);
+    }
+}
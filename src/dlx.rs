@@ -0,0 +1,282 @@
+use crate::solver::{run_, Tracker};
+use crate::{Budget, Sudoku, SudokuError, CAGE_COLS, CAGE_ROWS, COLS, ROWS};
+
+/// Number of constraint columns: one per cell, per row-value, per column-value and per
+/// cage-value pair.
+const CONSTRAINTS: usize = 4 * ROWS * COLS;
+
+/// Index of the root header node.
+const ROOT: usize = 0;
+
+/// A sparse exact-cover matrix using Knuth's Dancing Links representation.
+/// Nodes live in parallel vectors; nodes 1 through `CONSTRAINTS` are column headers.
+/// Each matrix row stands for placing one value in one cell.
+struct Matrix {
+    left: Vec<usize>,
+    right: Vec<usize>,
+    up: Vec<usize>,
+    down: Vec<usize>,
+    column: Vec<usize>,
+    // Index of the (row, col, val) placement that each node belongs to.
+    placement: Vec<usize>,
+    // Number of nodes remaining in each column, indexed by header node.
+    size: Vec<usize>,
+}
+
+impl Matrix {
+    /// Build the matrix for the given board.
+    /// Filled cells only contribute the placement of their value.
+    fn new(sudoku: &Sudoku) -> Self {
+        let mut matrix = Matrix {
+            left: Vec::new(),
+            right: Vec::new(),
+            up: Vec::new(),
+            down: Vec::new(),
+            column: Vec::new(),
+            placement: Vec::new(),
+            size: vec![0; CONSTRAINTS + 1],
+        };
+        for i in 0..=CONSTRAINTS {
+            matrix.push_node_(i, usize::MAX);
+            matrix.left[i] = if i == 0 { CONSTRAINTS } else { i - 1 };
+            matrix.right[i] = if i == CONSTRAINTS { 0 } else { i + 1 };
+        }
+
+        for (row, col, cell) in sudoku.cells() {
+            for val in 1..10u8 {
+                if cell.is_none() || cell == Some(val) {
+                    matrix.push_row_(row, col, val);
+                }
+            }
+        }
+        matrix
+    }
+
+    /// Append a node to column `column` and return its index.
+    fn push_node_(&mut self, column: usize, placement: usize) -> usize {
+        let node = self.column.len();
+        self.left.push(node);
+        self.right.push(node);
+        self.column.push(column);
+        self.placement.push(placement);
+        if column == node {
+            self.up.push(node);
+            self.down.push(node);
+        } else {
+            let last = self.up[column];
+            self.up.push(last);
+            self.down.push(column);
+            self.down[last] = node;
+            self.up[column] = node;
+            self.size[column] += 1;
+        }
+        node
+    }
+
+    /// Append the matrix row for placing `val` at (`row`, `col`).
+    fn push_row_(&mut self, row: usize, col: usize, val: u8) {
+        let v = (val - 1) as usize;
+        let cage = row / CAGE_ROWS * (COLS / CAGE_COLS) + col / CAGE_COLS;
+        let placement = (row * COLS + col) * 9 + v;
+        let columns = [
+            1 + row * COLS + col,
+            1 + ROWS * COLS + row * 9 + v,
+            1 + 2 * ROWS * COLS + col * 9 + v,
+            1 + 3 * ROWS * COLS + cage * 9 + v,
+        ];
+        let first = self.push_node_(columns[0], placement);
+        for &column in &columns[1..] {
+            let node = self.push_node_(column, placement);
+            let last = self.left[first];
+            self.left[node] = last;
+            self.right[node] = first;
+            self.right[last] = node;
+            self.left[first] = node;
+        }
+    }
+
+    /// Remove a column and every row intersecting it from the matrix.
+    fn cover_(&mut self, column: usize) {
+        self.right[self.left[column]] = self.right[column];
+        self.left[self.right[column]] = self.left[column];
+        let mut i = self.down[column];
+        while i != column {
+            let mut j = self.right[i];
+            while j != i {
+                self.down[self.up[j]] = self.down[j];
+                self.up[self.down[j]] = self.up[j];
+                self.size[self.column[j]] -= 1;
+                j = self.right[j];
+            }
+            i = self.down[i];
+        }
+    }
+
+    /// Undo `cover_`, restoring links in exactly the reverse order.
+    fn uncover_(&mut self, column: usize) {
+        let mut i = self.up[column];
+        while i != column {
+            let mut j = self.left[i];
+            while j != i {
+                self.size[self.column[j]] += 1;
+                self.down[self.up[j]] = j;
+                self.up[self.down[j]] = j;
+                j = self.left[j];
+            }
+            i = self.up[i];
+        }
+        self.right[self.left[column]] = column;
+        self.left[self.right[column]] = column;
+    }
+
+    /// Return the uncovered column with the fewest nodes, or None if every column is covered.
+    fn smallest_column_(&self) -> Option<usize> {
+        let mut best = None;
+        let mut best_size = usize::MAX;
+        let mut column = self.right[ROOT];
+        while column != ROOT {
+            if self.size[column] < best_size {
+                best = Some(column);
+                best_size = self.size[column];
+            }
+            column = self.right[column];
+        }
+        best
+    }
+
+    /// Run Algorithm X, calling `visit` with the chosen placements of every exact cover.
+    /// Return true as soon as `visit` returns true, and false once the search is exhausted
+    /// or the budget of `tracker` runs out.
+    fn search_(
+        &mut self,
+        chosen: &mut Vec<usize>,
+        tracker: &mut Tracker,
+        visit: &mut dyn FnMut(&[usize]) -> bool,
+    ) -> bool {
+        if !tracker.enter_(chosen.len()) {
+            return false;
+        }
+        let column = match self.smallest_column_() {
+            Some(column) => column,
+            None => return visit(chosen),
+        };
+        self.cover_(column);
+        let mut i = self.down[column];
+        while i != column {
+            chosen.push(self.placement[i]);
+            tracker.stats.placements += 1;
+            if self.size[column] > 1 {
+                tracker.stats.guesses += 1;
+            }
+            let mut j = self.right[i];
+            while j != i {
+                self.cover_(self.column[j]);
+                j = self.right[j];
+            }
+            let done = self.search_(chosen, tracker, visit);
+            let mut j = self.left[i];
+            while j != i {
+                self.uncover_(self.column[j]);
+                j = self.left[j];
+            }
+            chosen.pop();
+            if done {
+                self.uncover_(column);
+                return true;
+            }
+            tracker.stats.backtracks += 1;
+            if tracker.exceeded_() {
+                break;
+            }
+            i = self.down[i];
+        }
+        self.uncover_(column);
+        false
+    }
+}
+
+impl Sudoku {
+    /// Solve the sudoku board as an exact cover problem with Dancing Links.
+    /// Return an Ok if successful. If the board cannot be solved, return
+    /// `SudokuError::Unsolvable`.
+    /// This function mutates the internal board representation in-place.
+    pub fn solve_exact_cover(&mut self) -> Result<(), SudokuError> {
+        run_(&Budget::unlimited(), |tracker| {
+            self.solve_exact_cover_(tracker)
+        })
+        .result
+    }
+
+    /// Solve the sudoku board with Dancing Links, recording the work done in `tracker`.
+    /// Choosing the matrix row of a filled cell counts as a placement.
+    pub(crate) fn solve_exact_cover_(&mut self, tracker: &mut Tracker) -> Result<(), SudokuError> {
+        let mut solution = None;
+        Matrix::new(self).search_(&mut Vec::new(), tracker, &mut |chosen| {
+            solution = Some(chosen.to_vec());
+            true
+        });
+        let solution = solution.ok_or(SudokuError::Unsolvable)?;
+        for placement in solution {
+            let (cell, v) = (placement / 9, placement % 9);
+            let (row, col) = (cell / COLS, cell % COLS);
+            if self.cell(row, col).is_none() {
+                self.place_(row, col, v as u8 + 1);
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{boards, Method, SolutionCount};
+
+    #[test]
+    fn solve_exact_cover_matches_backtracking() {
+        for initial in [
+            boards::VALID_PUZZLE_1,
+            boards::VALID_PUZZLE_2,
+            boards::VALID_PUZZLE_3,
+        ]
+        .iter()
+        {
+            let mut expected = Sudoku::new(initial.to_vec()).unwrap();
+            expected.solve_using(Method::Backtracking).unwrap();
+            let mut puzzle = Sudoku::new(initial.to_vec()).unwrap();
+            puzzle.solve_using(Method::ExactCover).unwrap();
+            assert_eq!(puzzle, expected);
+        }
+    }
+
+    #[test]
+    fn solve_exact_cover_empty_board() {
+        let mut puzzle = Sudoku::new(Vec::new()).unwrap();
+        puzzle.solve_exact_cover().unwrap();
+        assert!(puzzle.verify());
+    }
+
+    #[test]
+    fn solve_exact_cover_unsolvable() {
+        let mut puzzle = Sudoku::new(boards::UNSOLVABLE_PUZZLE.to_vec()).unwrap();
+        let original = puzzle.clone();
+        assert_eq!(puzzle.solve_exact_cover(), Err(SudokuError::Unsolvable));
+        assert_eq!(puzzle, original);
+    }
+
+    #[test]
+    fn count_exact_covers() {
+        let mut initial = boards::VALID_SOLUTION.to_vec();
+        initial.retain(|&(row, _, _)| row > 1);
+        let puzzle = Sudoku::new(initial).unwrap();
+        let mut count = 0;
+        let budget = Budget::unlimited();
+        let mut tracker = Tracker::new(&budget);
+        Matrix::new(&puzzle).search_(&mut Vec::new(), &mut tracker, &mut |chosen| {
+            assert_eq!(chosen.len(), 81);
+            count += 1;
+            false
+        });
+        assert_eq!(puzzle.count_solutions(100), SolutionCount::Exact(count));
+    }
+}
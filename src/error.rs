@@ -0,0 +1,119 @@
+use std::error::Error;
+use std::fmt::{self, Display, Formatter};
+
+use crate::Conflict;
+
+/// Errors produced while building or solving a sudoku board.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SudokuError {
+    /// The given value was placed at a position outside of the 9-by-9 board.
+    OutOfBounds { row: usize, col: usize, val: u8 },
+    /// The given position lies outside of the 9-by-9 board.
+    InvalidPosition { row: usize, col: usize },
+    /// The given value is not an integer between 1 and 9.
+    InvalidValue { row: usize, col: usize, val: u8 },
+    /// A value was placed twice at the same position.
+    DuplicatePlacement { row: usize, col: usize, val: u8 },
+    /// Some values share a row, column or cage with an equal value.
+    /// Every conflicting pair is reported.
+    ConflictingGivens(Vec<Conflict>),
+    /// The given cell holds a given, which cannot be changed.
+    GivenCell { row: usize, col: usize },
+    /// The given cell holds a value, so it has no candidates to change.
+    FilledCell { row: usize, col: usize },
+    /// Placing a value would repeat it within a row, column or cage.
+    ConflictingPlacement(Conflict),
+    /// The board has no solution.
+    Unsolvable,
+    /// The solver ran out of its node, time or cancellation budget before finishing.
+    BudgetExceeded,
+    /// A character that is neither a digit nor a blank was found while parsing.
+    /// The offset counts characters from the start of the input.
+    InvalidCharacter { offset: usize, found: char },
+    /// The parsed input did not describe exactly 81 cells.
+    InvalidLength { len: usize },
+    /// A saved search state could not be restored because of the given line.
+    InvalidSearchState { line: usize },
+    /// A DIMACS model contained a token that is not a literal of the sudoku encoding.
+    InvalidModel { token: String },
+    /// A DIMACS model left the given cell without a value.
+    IncompleteModel { row: usize, col: usize },
+}
+
+impl Display for SudokuError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            SudokuError::OutOfBounds { row, col, val } => write!(
+                f,
+                "Value: {} at position ({}, {}) is out of bounds.",
+                val, row, col
+            ),
+            SudokuError::InvalidPosition { row, col } => {
+                write!(f, "Position ({}, {}) is out of bounds.", row, col)
+            }
+            SudokuError::InvalidValue { row, col, val } => write!(
+                f,
+                "Value: {} at position ({}, {}) is invalid.",
+                val, row, col
+            ),
+            SudokuError::DuplicatePlacement { row, col, val } => write!(
+                f,
+                "Value: {} cannot be placed at position ({}, {}) because a value already exists.",
+                val, row, col
+            ),
+            SudokuError::ConflictingGivens(conflicts) => {
+                write!(f, "Puzzle contains conflicting values:")?;
+                for conflict in conflicts {
+                    write!(
+                        f,
+                        " {} at ({}, {}) and ({}, {});",
+                        conflict.val,
+                        conflict.first.0,
+                        conflict.first.1,
+                        conflict.second.0,
+                        conflict.second.1
+                    )?;
+                }
+                Ok(())
+            }
+            SudokuError::GivenCell { row, col } => write!(
+                f,
+                "Position ({}, {}) holds a given and cannot be changed.",
+                row, col
+            ),
+            SudokuError::FilledCell { row, col } => write!(
+                f,
+                "Position ({}, {}) holds a value and has no candidates.",
+                row, col
+            ),
+            SudokuError::ConflictingPlacement(conflict) => write!(
+                f,
+                "Value: {} conflicts with ({}, {}) and ({}, {}).",
+                conflict.val,
+                conflict.first.0,
+                conflict.first.1,
+                conflict.second.0,
+                conflict.second.1
+            ),
+            SudokuError::Unsolvable => write!(f, "Puzzle has no solution."),
+            SudokuError::BudgetExceeded => write!(f, "Solver exceeded its budget."),
+            SudokuError::InvalidCharacter { offset, found } => {
+                write!(f, "Unexpected character '{}' at offset {}.", found, offset)
+            }
+            SudokuError::InvalidLength { len } => {
+                write!(f, "Expected 81 cells but found {}.", len)
+            }
+            SudokuError::InvalidSearchState { line } => {
+                write!(f, "Saved search state is invalid at line {}.", line)
+            }
+            SudokuError::InvalidModel { token } => {
+                write!(f, "Model contains invalid literal '{}'.", token)
+            }
+            SudokuError::IncompleteModel { row, col } => {
+                write!(f, "Model leaves position ({}, {}) empty.", row, col)
+            }
+        }
+    }
+}
+
+impl Error for SudokuError {}
@@ -0,0 +1,56 @@
+//! Copyright (c) 2020, Shoyo Inokuchi
+//!
+//! A simple sudoku solver written in Rust.
+//! Feel free to to refer to the repository at: <https://github.com/shoyo/sudoku> for more
+//! information.
+//!
+//! ```
+//! use sudoku::{boards, Sudoku};
+//!
+//! let mut puzzle = Sudoku::new(boards::VALID_PUZZLE_1.to_vec()).unwrap();
+//! puzzle.solve().unwrap();
+//! assert!(puzzle.verify());
+//! ```
+
+pub mod boards;
+mod budget;
+mod candidates;
+mod digits;
+mod dimacs;
+mod dlx;
+mod error;
+mod logic;
+mod notation;
+mod propagate;
+mod render;
+mod search;
+mod select;
+mod solutions;
+mod solver;
+mod sudoku;
+mod unit;
+
+pub use crate::budget::Budget;
+pub use crate::candidates::Candidates;
+pub use crate::digits::{Digits, DigitsIter};
+pub use crate::error::SudokuError;
+pub use crate::logic::{Deduction, LogicalSolver, Status, Technique, Trace};
+pub use crate::render::{Render, Style};
+pub use crate::search::{Search, Step};
+pub use crate::select::{CellSelector, FirstOpen, MinimumRemaining};
+pub use crate::solutions::Solutions;
+pub use crate::solver::{
+    BacktrackingSolver, ExactCoverSolver, IterativeSolver, Method, Outcome, PropagationSolver,
+    SolveStats, Solver,
+};
+pub use crate::sudoku::{Cells, Check, Origin, SolutionCount, Sudoku};
+pub use crate::unit::{Conflict, Unit};
+
+/// Number of rows on the board.
+pub const ROWS: usize = 9;
+/// Number of columns on the board.
+pub const COLS: usize = 9;
+/// Number of rows in a single cage.
+pub const CAGE_ROWS: usize = 3;
+/// Number of columns in a single cage.
+pub const CAGE_COLS: usize = 3;
@@ -0,0 +1,257 @@
+use crate::logic::{subsets_, Deduction, Technique};
+use crate::{Candidates, Digits, Unit, CAGE_COLS, CAGE_ROWS, COLS, ROWS};
+
+/// The variants of a fish pattern.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(super) enum Kind {
+    /// The base lines hold the value only within the cover lines.
+    Basic,
+    /// Some base cells lie outside the cover lines, all within one cage.
+    Finned,
+    /// A finned fish in which some base line has only one cell within the cover lines,
+    /// so the pattern would not be a fish at all without its fins.
+    Sashimi,
+}
+
+/// Find `size` base lines (rows or columns) whose places for a value lie within `size`
+/// cover lines crossing them. The value then fills each base line in a different cover
+/// line, so it is eliminated from the rest of the cover lines.
+/// A finned or sashimi fish also has fins: extra places in the base lines, all in one
+/// cage. Either a fin or the fish holds the value, so only cover cells inside the fins'
+/// cage lose it.
+pub(super) fn fish(
+    candidates: &Candidates,
+    technique: Technique,
+    size: usize,
+    kind: Kind,
+) -> Option<Deduction> {
+    for val in 1..10u8 {
+        for &by_rows in &[true, false] {
+            if let Some(deduction) = fish_(candidates, technique, size, kind, val, by_rows) {
+                return Some(deduction);
+            }
+        }
+    }
+    None
+}
+
+/// Search for a fish on the given value with rows as base lines if `by_rows` is set, and
+/// columns otherwise.
+fn fish_(
+    candidates: &Candidates,
+    technique: Technique,
+    size: usize,
+    kind: Kind,
+    val: u8,
+    by_rows: bool,
+) -> Option<Deduction> {
+    // Cell at position `cross` along base line `line`.
+    let cell = |line: usize, cross: usize| {
+        if by_rows {
+            (line, cross)
+        } else {
+            (cross, line)
+        }
+    };
+    let line_unit = |line: usize| {
+        if by_rows {
+            Unit::Row(line)
+        } else {
+            Unit::Col(line)
+        }
+    };
+    let cross_unit = |cross: usize| {
+        if by_rows {
+            Unit::Col(cross)
+        } else {
+            Unit::Row(cross)
+        }
+    };
+    let cage_of = |(row, col): (usize, usize)| (row / CAGE_ROWS, col / CAGE_COLS);
+
+    // Bit `cross` of `places[line]` is set iff the value may go in that cell.
+    let mut places = [0u16; ROWS];
+    for (line, mask) in places.iter_mut().enumerate() {
+        for cross in 0..COLS {
+            let (row, col) = cell(line, cross);
+            if candidates.contains(row, col, val) {
+                *mask |= 1 << cross;
+            }
+        }
+    }
+    let lines: Vec<_> = (0..ROWS)
+        .filter(|&line| places[line].count_ones() >= 2)
+        .collect();
+
+    for base in subsets_(lines.len(), size) {
+        let base: Vec<_> = base.iter().map(|&i| lines[i]).collect();
+        let union = base.iter().fold(0, |union, &line| union | places[line]);
+        let spread = union.count_ones() as usize;
+        let covers: Vec<u16> = match kind {
+            Kind::Basic if spread == size => vec![union],
+            Kind::Finned | Kind::Sashimi if spread > size && spread <= size + CAGE_COLS => {
+                let crosses: Vec<_> = (0..COLS).filter(|&c| union & (1 << c) != 0).collect();
+                subsets_(crosses.len(), size)
+                    .map(|cover| cover.iter().fold(0, |mask, &i| mask | 1 << crosses[i]))
+                    .collect()
+            }
+            _ => continue,
+        };
+
+        for cover in covers {
+            let within: Vec<_> = base
+                .iter()
+                .map(|&line| (places[line] & cover).count_ones())
+                .collect();
+            if within.contains(&0) {
+                continue;
+            }
+            let sashimi = within.contains(&1);
+            match kind {
+                Kind::Finned if sashimi => continue,
+                Kind::Sashimi if !sashimi => continue,
+                _ => {}
+            }
+
+            let mut body = Vec::new();
+            let mut fins = Vec::new();
+            for &line in &base {
+                for cross in (0..COLS).filter(|&c| places[line] & (1 << c) != 0) {
+                    if cover & (1 << cross) != 0 {
+                        body.push(cell(line, cross));
+                    } else {
+                        fins.push(cell(line, cross));
+                    }
+                }
+            }
+            let fin_cage = fins.first().map(|&fin| cage_of(fin));
+            if fins.iter().any(|&fin| Some(cage_of(fin)) != fin_cage) {
+                continue;
+            }
+
+            let mut eliminations = Vec::new();
+            for cross in (0..COLS).filter(|&c| cover & (1 << c) != 0) {
+                for line in (0..ROWS).filter(|line| !base.contains(line)) {
+                    let (row, col) = cell(line, cross);
+                    let seen = fin_cage.is_none() || fin_cage == Some(cage_of((row, col)));
+                    if seen && candidates.contains(row, col, val) {
+                        eliminations.push((row, col, val));
+                    }
+                }
+            }
+            eliminations.sort_unstable();
+            if eliminations.is_empty() {
+                continue;
+            }
+
+            let mut units: Vec<_> = base.iter().map(|&line| line_unit(line)).collect();
+            units.extend((0..COLS).filter(|&c| cover & (1 << c) != 0).map(cross_unit));
+            return Some(Deduction {
+                technique,
+                units,
+                cells: body,
+                fins,
+                digits: Digits::single(val),
+                placements: Vec::new(),
+                eliminations,
+            });
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::logic::tests::narrowed;
+
+    /// Return the eliminations that leave `val` only at the given columns of the given
+    /// rows.
+    fn only(val: u8, rows: &[(usize, &[usize])]) -> Vec<(usize, usize, u8)> {
+        let mut eliminations = Vec::new();
+        for &(row, cols) in rows {
+            for col in (0..COLS).filter(|col| !cols.contains(col)) {
+                eliminations.push((row, col, val));
+            }
+        }
+        eliminations
+    }
+
+    #[test]
+    fn find_x_wing() {
+        let candidates = narrowed(only(4, &[(1, &[2, 7]), (6, &[2, 7])]));
+        let deduction = fish(&candidates, Technique::XWing, 2, Kind::Basic).unwrap();
+        assert_eq!(
+            deduction.units,
+            vec![Unit::Row(1), Unit::Row(6), Unit::Col(2), Unit::Col(7)]
+        );
+        assert_eq!(deduction.cells, vec![(1, 2), (1, 7), (6, 2), (6, 7)]);
+        assert!(deduction.fins.is_empty());
+        assert_eq!(deduction.digits, Digits::single(4));
+        assert_eq!(deduction.eliminations.len(), 14);
+        assert!(deduction.eliminations.contains(&(0, 2, 4)));
+        assert!(!deduction.eliminations.contains(&(6, 7, 4)));
+        assert!(fish(&candidates, Technique::FinnedXWing, 2, Kind::Finned).is_none());
+    }
+
+    #[test]
+    fn find_column_x_wing() {
+        let candidates = narrowed(
+            (0..ROWS)
+                .filter(|&row| row != 3 && row != 5)
+                .flat_map(|row| vec![(row, 0, 7), (row, 4, 7)]),
+        );
+        let deduction = fish(&candidates, Technique::XWing, 2, Kind::Basic).unwrap();
+        assert_eq!(
+            deduction.units,
+            vec![Unit::Col(0), Unit::Col(4), Unit::Row(3), Unit::Row(5)]
+        );
+        assert!(deduction.eliminations.contains(&(3, 8, 7)));
+    }
+
+    #[test]
+    fn find_swordfish() {
+        let candidates = narrowed(only(9, &[(0, &[1, 5]), (4, &[5, 7]), (8, &[1, 7])]));
+        assert!(fish(&candidates, Technique::XWing, 2, Kind::Basic).is_none());
+        let deduction = fish(&candidates, Technique::Swordfish, 3, Kind::Basic).unwrap();
+        assert_eq!(
+            deduction.units[..3],
+            [Unit::Row(0), Unit::Row(4), Unit::Row(8)]
+        );
+        assert_eq!(deduction.cells.len(), 6);
+        assert_eq!(deduction.eliminations.len(), 18);
+    }
+
+    #[test]
+    fn find_jellyfish() {
+        let candidates = narrowed(only(
+            5,
+            &[(0, &[1, 3]), (2, &[3, 5]), (4, &[5, 7]), (6, &[7, 1])],
+        ));
+        assert!(fish(&candidates, Technique::Swordfish, 3, Kind::Basic).is_none());
+        let deduction = fish(&candidates, Technique::Jellyfish, 4, Kind::Basic).unwrap();
+        assert_eq!(deduction.units.len(), 8);
+        assert_eq!(deduction.eliminations.len(), 20);
+    }
+
+    #[test]
+    fn find_finned_x_wing() {
+        let candidates = narrowed(only(2, &[(1, &[1, 7]), (7, &[1, 7, 8])]));
+        assert!(fish(&candidates, Technique::XWing, 2, Kind::Basic).is_none());
+        assert!(fish(&candidates, Technique::SashimiXWing, 2, Kind::Sashimi).is_none());
+        let deduction = fish(&candidates, Technique::FinnedXWing, 2, Kind::Finned).unwrap();
+        assert_eq!(deduction.cells, vec![(1, 1), (1, 7), (7, 1), (7, 7)]);
+        assert_eq!(deduction.fins, vec![(7, 8)]);
+        assert_eq!(deduction.eliminations, vec![(6, 7, 2), (8, 7, 2)]);
+    }
+
+    #[test]
+    fn find_sashimi_x_wing() {
+        let candidates = narrowed(only(6, &[(0, &[0, 6]), (4, &[6, 7])]));
+        assert!(fish(&candidates, Technique::FinnedXWing, 2, Kind::Finned).is_none());
+        let deduction = fish(&candidates, Technique::SashimiXWing, 2, Kind::Sashimi).unwrap();
+        assert_eq!(deduction.cells, vec![(0, 0), (0, 6), (4, 6)]);
+        assert_eq!(deduction.fins, vec![(4, 7)]);
+        assert_eq!(deduction.eliminations, vec![(3, 6, 6), (5, 6, 6)]);
+    }
+}
@@ -0,0 +1,144 @@
+use crate::logic::{Deduction, Technique};
+use crate::{Candidates, Digits, Unit, CAGE_COLS, CAGE_ROWS};
+
+/// Find a value whose places in a cage all lie in one row or column.
+/// The value must go in that cage, so it is eliminated from the rest of the line.
+pub(super) fn pointing(candidates: &Candidates) -> Option<Deduction> {
+    let cages = Unit::all().filter(|unit| matches!(unit, Unit::Cage(..)));
+    for cage in cages {
+        for val in candidates.unit_digits(cage) {
+            let cells: Vec<_> = candidates.cells_with(cage, val).collect();
+            let (row, col) = cells[0];
+            let lines = [
+                (Unit::Row(row), cells.iter().all(|&(r, _)| r == row)),
+                (Unit::Col(col), cells.iter().all(|&(_, c)| c == col)),
+            ];
+            for &(line, confined) in &lines {
+                if !confined {
+                    continue;
+                }
+                if let Some(deduction) =
+                    locked_(candidates, Technique::Pointing, cage, line, &cells, val)
+                {
+                    return Some(deduction);
+                }
+            }
+        }
+    }
+    None
+}
+
+/// Find a value whose places in a row or column all lie in one cage.
+/// The value must go in that line, so it is eliminated from the rest of the cage.
+pub(super) fn claiming(candidates: &Candidates) -> Option<Deduction> {
+    let lines = Unit::all().filter(|unit| !matches!(unit, Unit::Cage(..)));
+    for line in lines {
+        for val in candidates.unit_digits(line) {
+            let cells: Vec<_> = candidates.cells_with(line, val).collect();
+            let (row, col) = cells[0];
+            let cage = Unit::Cage(row / CAGE_ROWS, col / CAGE_COLS);
+            let confined = cells
+                .iter()
+                .all(|&(r, c)| Unit::Cage(r / CAGE_ROWS, c / CAGE_COLS) == cage);
+            if !confined {
+                continue;
+            }
+            if let Some(deduction) =
+                locked_(candidates, Technique::Claiming, line, cage, &cells, val)
+            {
+                return Some(deduction);
+            }
+        }
+    }
+    None
+}
+
+/// Return the deduction for `val` being locked into the intersection of `base` and
+/// `cover` at `cells`, or None if it eliminates nothing from the rest of `cover`.
+fn locked_(
+    candidates: &Candidates,
+    technique: Technique,
+    base: Unit,
+    cover: Unit,
+    cells: &[(usize, usize)],
+    val: u8,
+) -> Option<Deduction> {
+    let eliminations: Vec<_> = candidates
+        .cells_with(cover, val)
+        .filter(|cell| !cells.contains(cell))
+        .map(|(row, col)| (row, col, val))
+        .collect();
+    if eliminations.is_empty() {
+        return None;
+    }
+    Some(Deduction {
+        technique,
+        units: vec![base, cover],
+        cells: cells.to_vec(),
+        fins: Vec::new(),
+        digits: Digits::single(val),
+        placements: Vec::new(),
+        eliminations,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::logic::tests::narrowed;
+    use crate::{COLS, ROWS};
+
+    #[test]
+    fn find_pointing_pair() {
+        // Leave 5 in the centre cage only on its middle row.
+        let cage = Unit::Cage(1, 1).cells();
+        let candidates = narrowed(
+            cage.iter()
+                .filter(|&&(row, col)| row != 4 || col == 5)
+                .map(|&(row, col)| (row, col, 5)),
+        );
+        let deduction = pointing(&candidates).unwrap();
+        assert_eq!(deduction.technique, Technique::Pointing);
+        assert_eq!(deduction.units, vec![Unit::Cage(1, 1), Unit::Row(4)]);
+        assert_eq!(deduction.cells, vec![(4, 3), (4, 4)]);
+        assert_eq!(deduction.digits, Digits::single(5));
+        assert_eq!(
+            deduction.eliminations,
+            vec![
+                (4, 0, 5),
+                (4, 1, 5),
+                (4, 2, 5),
+                (4, 6, 5),
+                (4, 7, 5),
+                (4, 8, 5)
+            ]
+        );
+        assert!(claiming(&candidates).is_none());
+    }
+
+    #[test]
+    fn find_pointing_column() {
+        let cage = Unit::Cage(2, 0).cells();
+        let candidates = narrowed(
+            cage.iter()
+                .filter(|&&(_, col)| col != 2)
+                .map(|&(row, col)| (row, col, 8)),
+        );
+        let deduction = pointing(&candidates).unwrap();
+        assert_eq!(deduction.units, vec![Unit::Cage(2, 0), Unit::Col(2)]);
+        assert_eq!(deduction.eliminations.len(), ROWS - CAGE_ROWS);
+    }
+
+    #[test]
+    fn find_claiming() {
+        // Leave 3 in the first row only in the top-right cage.
+        let candidates = narrowed((0..COLS - CAGE_COLS).map(|col| (0, col, 3)));
+        assert!(pointing(&candidates).is_none());
+        let deduction = claiming(&candidates).unwrap();
+        assert_eq!(deduction.technique, Technique::Claiming);
+        assert_eq!(deduction.units, vec![Unit::Row(0), Unit::Cage(0, 2)]);
+        assert_eq!(deduction.cells, vec![(0, 6), (0, 7), (0, 8)]);
+        assert_eq!(deduction.eliminations.len(), 6);
+        assert!(deduction.eliminations.contains(&(2, 8, 3)));
+    }
+}
@@ -0,0 +1,399 @@
+//! Human-style solving with named deduction techniques.
+//! Each technique only looks at the pencil marks of a board and never guesses, so every
+//! step can be explained to a reader.
+
+use std::fmt::{self, Display, Formatter};
+
+use crate::logic::fish::Kind;
+use crate::{Candidates, Digits, Sudoku, Unit};
+
+mod fish;
+mod intersections;
+mod singles;
+mod subsets;
+
+/// A named deduction technique, listed by `Technique::all` from simplest to hardest.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Technique {
+    /// A cell with only one candidate left.
+    NakedSingle,
+    /// A value with only one possible cell left in a row, column or cage.
+    HiddenSingle,
+    /// A value whose places in a cage all lie in one row or column, so it can be
+    /// eliminated from the rest of that line.
+    Pointing,
+    /// A value whose places in a row or column all lie in one cage, so it can be
+    /// eliminated from the rest of that cage. Also known as box-line reduction.
+    Claiming,
+    /// Two cells in a unit whose candidates together hold only two values.
+    NakedPair,
+    /// Two values in a unit that together fit in only two cells.
+    HiddenPair,
+    /// Three cells in a unit whose candidates together hold only three values.
+    NakedTriple,
+    /// Three values in a unit that together fit in only three cells.
+    HiddenTriple,
+    /// Four cells in a unit whose candidates together hold only four values.
+    NakedQuad,
+    /// Four values in a unit that together fit in only four cells.
+    HiddenQuad,
+    /// Two rows whose places for a value lie in the same two columns, or the reverse.
+    XWing,
+    /// Three rows whose places for a value lie in the same three columns, or the reverse.
+    Swordfish,
+    /// Four rows whose places for a value lie in the same four columns, or the reverse.
+    Jellyfish,
+    /// An X-Wing with extra places for the value in one cage.
+    FinnedXWing,
+    /// A finned X-Wing that is only an X-Wing with its fins included.
+    SashimiXWing,
+    /// A Swordfish with extra places for the value in one cage.
+    FinnedSwordfish,
+    /// A finned Swordfish that is only a Swordfish with its fins included.
+    SashimiSwordfish,
+    /// A Jellyfish with extra places for the value in one cage.
+    FinnedJellyfish,
+    /// A finned Jellyfish that is only a Jellyfish with its fins included.
+    SashimiJellyfish,
+}
+
+impl Technique {
+    /// Return every technique, in the order `LogicalSolver::new` tries them.
+    pub fn all() -> &'static [Technique] {
+        &[
+            Technique::NakedSingle,
+            Technique::HiddenSingle,
+            Technique::Pointing,
+            Technique::Claiming,
+            Technique::NakedPair,
+            Technique::HiddenPair,
+            Technique::NakedTriple,
+            Technique::HiddenTriple,
+            Technique::NakedQuad,
+            Technique::HiddenQuad,
+            Technique::XWing,
+            Technique::Swordfish,
+            Technique::Jellyfish,
+            Technique::FinnedXWing,
+            Technique::SashimiXWing,
+            Technique::FinnedSwordfish,
+            Technique::SashimiSwordfish,
+            Technique::FinnedJellyfish,
+            Technique::SashimiJellyfish,
+        ]
+    }
+
+    /// Return the usual name of the technique.
+    pub fn name(self) -> &'static str {
+        match self {
+            Technique::NakedSingle => "Naked Single",
+            Technique::HiddenSingle => "Hidden Single",
+            Technique::Pointing => "Pointing",
+            Technique::Claiming => "Claiming",
+            Technique::NakedPair => "Naked Pair",
+            Technique::HiddenPair => "Hidden Pair",
+            Technique::NakedTriple => "Naked Triple",
+            Technique::HiddenTriple => "Hidden Triple",
+            Technique::NakedQuad => "Naked Quad",
+            Technique::HiddenQuad => "Hidden Quad",
+            Technique::XWing => "X-Wing",
+            Technique::Swordfish => "Swordfish",
+            Technique::Jellyfish => "Jellyfish",
+            Technique::FinnedXWing => "Finned X-Wing",
+            Technique::SashimiXWing => "Sashimi X-Wing",
+            Technique::FinnedSwordfish => "Finned Swordfish",
+            Technique::SashimiSwordfish => "Sashimi Swordfish",
+            Technique::FinnedJellyfish => "Finned Jellyfish",
+            Technique::SashimiJellyfish => "Sashimi Jellyfish",
+        }
+    }
+
+    /// Return the first deduction this technique finds, if any.
+    fn find_(self, candidates: &Candidates) -> Option<Deduction> {
+        match self {
+            Technique::NakedSingle => singles::naked_single(candidates),
+            Technique::HiddenSingle => singles::hidden_single(candidates),
+            Technique::Pointing => intersections::pointing(candidates),
+            Technique::Claiming => intersections::claiming(candidates),
+            Technique::NakedPair => subsets::naked_subset(candidates, self, 2),
+            Technique::HiddenPair => subsets::hidden_subset(candidates, self, 2),
+            Technique::NakedTriple => subsets::naked_subset(candidates, self, 3),
+            Technique::HiddenTriple => subsets::hidden_subset(candidates, self, 3),
+            Technique::NakedQuad => subsets::naked_subset(candidates, self, 4),
+            Technique::HiddenQuad => subsets::hidden_subset(candidates, self, 4),
+            Technique::XWing => fish::fish(candidates, self, 2, Kind::Basic),
+            Technique::Swordfish => fish::fish(candidates, self, 3, Kind::Basic),
+            Technique::Jellyfish => fish::fish(candidates, self, 4, Kind::Basic),
+            Technique::FinnedXWing => fish::fish(candidates, self, 2, Kind::Finned),
+            Technique::SashimiXWing => fish::fish(candidates, self, 2, Kind::Sashimi),
+            Technique::FinnedSwordfish => fish::fish(candidates, self, 3, Kind::Finned),
+            Technique::SashimiSwordfish => fish::fish(candidates, self, 3, Kind::Sashimi),
+            Technique::FinnedJellyfish => fish::fish(candidates, self, 4, Kind::Finned),
+            Technique::SashimiJellyfish => fish::fish(candidates, self, 4, Kind::Sashimi),
+        }
+    }
+}
+
+impl Display for Technique {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+/// Return every way of choosing `size` of the indexes below `len`, each in increasing
+/// order. `len` must be at most 16.
+fn subsets_(len: usize, size: usize) -> impl Iterator<Item = Vec<usize>> {
+    (0..1u32 << len)
+        .filter(move |mask| mask.count_ones() as usize == size)
+        .map(move |mask| (0..len).filter(|i| mask & (1 << i) != 0).collect())
+}
+
+/// A single step found by a technique.
+/// Placing a value also removes it from the candidates of every cell sharing a unit with
+/// it; those removals are implied and not listed in `eliminations`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Deduction {
+    pub technique: Technique,
+    /// Units the pattern was found in. For a fish, the base lines come first, followed
+    /// by the cover lines.
+    pub units: Vec<Unit>,
+    /// Cells forming the pattern, as (row index, column index).
+    pub cells: Vec<(usize, usize)>,
+    /// Extra cells the pattern depends on, such as the fins of a finned fish.
+    pub fins: Vec<(usize, usize)>,
+    /// Values the pattern is about.
+    pub digits: Digits,
+    /// Values that can be placed, as (row index, column index, value).
+    pub placements: Vec<(usize, usize, u8)>,
+    /// Candidates that can be removed, as (row index, column index, value).
+    pub eliminations: Vec<(usize, usize, u8)>,
+}
+
+impl Display for Deduction {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:", self.technique)?;
+        for &(row, col, val) in &self.placements {
+            write!(f, " ({}, {}) = {};", row, col, val)?;
+        }
+        for &(row, col, val) in &self.eliminations {
+            write!(f, " ({}, {}) <> {};", row, col, val)?;
+        }
+        Ok(())
+    }
+}
+
+/// How a logical solve ended.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Status {
+    /// Every cell is filled.
+    Solved,
+    /// No enabled technique applies, so solving further would need a guess or a harder
+    /// technique.
+    Stuck,
+    /// The board has a repeated value, an empty cell with no candidates, or a unit with
+    /// no place left for a missing value.
+    Contradiction,
+}
+
+/// The steps taken by `LogicalSolver::solve`, in order, and where they led.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Trace {
+    pub steps: Vec<Deduction>,
+    pub status: Status,
+    /// Pencil marks left once the solver stopped.
+    pub candidates: Candidates,
+}
+
+/// A solver that only uses named deduction techniques and never guesses.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LogicalSolver {
+    techniques: Vec<Technique>,
+}
+
+impl LogicalSolver {
+    /// Create a solver using every technique in `Technique::all`.
+    pub fn new() -> Self {
+        Self::with_techniques(Technique::all())
+    }
+
+    /// Create a solver using only the given techniques, tried in the given order.
+    pub fn with_techniques(techniques: &[Technique]) -> Self {
+        Self {
+            techniques: techniques.to_vec(),
+        }
+    }
+
+    /// Return the first deduction found by the enabled techniques, trying them in order.
+    pub fn next_step(&self, candidates: &Candidates) -> Option<Deduction> {
+        self.techniques
+            .iter()
+            .find_map(|technique| technique.find_(candidates))
+    }
+
+    /// Apply deductions to the board until it is solved or no technique applies.
+    /// After every step the techniques are tried again from the first.
+    /// The board keeps every placement made, even if it could not be solved.
+    pub fn solve(&self, sudoku: &mut Sudoku) -> Trace {
+        let mut candidates = Candidates::new(sudoku);
+        let mut steps = Vec::new();
+        let status = loop {
+            if !sudoku.consistent_() || Self::contradiction_(sudoku, &candidates) {
+                break Status::Contradiction;
+            }
+            if sudoku.open_cells().next().is_none() {
+                break Status::Solved;
+            }
+            let deduction = match self.next_step(&candidates) {
+                Some(deduction) => deduction,
+                None => break Status::Stuck,
+            };
+            for &(row, col, val) in &deduction.placements {
+                sudoku.place_(row, col, val);
+                candidates.place_(row, col, val);
+            }
+            for &(row, col, val) in &deduction.eliminations {
+                candidates.eliminate_(row, col, val);
+            }
+            steps.push(deduction);
+        };
+        Trace {
+            steps,
+            status,
+            candidates,
+        }
+    }
+
+    /// Return true iff an empty cell has no candidates, or a unit has no place left for
+    /// one of its missing values.
+    fn contradiction_(sudoku: &Sudoku, candidates: &Candidates) -> bool {
+        sudoku
+            .open_cells()
+            .any(|(row, col)| candidates.get(row, col).is_empty())
+            || Unit::all().any(|unit| {
+                let missing = !sudoku.unit_digits_(unit);
+                candidates.unit_digits(unit) & missing != missing
+            })
+    }
+}
+
+impl Default for LogicalSolver {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::boards;
+
+    /// Return the candidates of an empty board with the given values eliminated.
+    pub(super) fn narrowed<I>(eliminations: I) -> Candidates
+    where
+        I: IntoIterator<Item = (usize, usize, u8)>,
+    {
+        let mut candidates = Candidates::new(&Sudoku::new(Vec::new()).unwrap());
+        for (row, col, val) in eliminations {
+            candidates.eliminate(row, col, val).unwrap();
+        }
+        candidates
+    }
+
+    #[test]
+    fn solve_with_singles() {
+        let original = Sudoku::new(boards::EASY_PUZZLE.to_vec()).unwrap();
+        let mut puzzle = original.clone();
+        let trace = LogicalSolver::new().solve(&mut puzzle);
+        assert_eq!(trace.status, Status::Solved);
+        assert_eq!(trace.steps.len(), 27);
+        assert!(trace.candidates.is_empty());
+        assert!(puzzle.verify());
+        assert_eq!(puzzle, original.solved().unwrap());
+    }
+
+    #[test]
+    fn solve_with_naked_singles_only() {
+        let mut puzzle = Sudoku::new(boards::EASY_PUZZLE.to_vec()).unwrap();
+        let solver = LogicalSolver::with_techniques(&[Technique::NakedSingle]);
+        let trace = solver.solve(&mut puzzle);
+        assert_eq!(trace.status, Status::Solved);
+        assert!(trace
+            .steps
+            .iter()
+            .all(|step| step.technique == Technique::NakedSingle));
+    }
+
+    #[test]
+    fn solve_bundled_puzzles() {
+        for initial in [
+            boards::VALID_PUZZLE_1,
+            boards::VALID_PUZZLE_2,
+            boards::VALID_PUZZLE_3,
+        ]
+        .iter()
+        {
+            let expected = Sudoku::new(initial.to_vec()).unwrap().solved().unwrap();
+            let mut puzzle = Sudoku::new(initial.to_vec()).unwrap();
+            let trace = LogicalSolver::new().solve(&mut puzzle);
+            assert_eq!(trace.status, Status::Solved);
+            assert_eq!(puzzle, expected);
+        }
+    }
+
+    #[test]
+    fn techniques_needed_beyond_singles() {
+        let singles = &Technique::all()[..2];
+        let mut puzzle = Sudoku::new(boards::VALID_PUZZLE_2.to_vec()).unwrap();
+        let trace = LogicalSolver::with_techniques(singles).solve(&mut puzzle);
+        assert_eq!(trace.status, Status::Stuck);
+        assert!(puzzle.open_cells().next().is_some());
+
+        let uses = |techniques: &[Technique], technique: Technique| {
+            let mut puzzle = Sudoku::new(boards::VALID_PUZZLE_2.to_vec()).unwrap();
+            let trace = LogicalSolver::with_techniques(techniques).solve(&mut puzzle);
+            assert_eq!(trace.status, Status::Solved);
+            trace.steps.iter().any(|step| step.technique == technique)
+        };
+        assert!(uses(Technique::all(), Technique::Pointing));
+        let without_intersections: Vec<_> = Technique::all()
+            .iter()
+            .copied()
+            .filter(|&t| t != Technique::Pointing && t != Technique::Claiming)
+            .collect();
+        assert!(uses(&without_intersections, Technique::HiddenPair));
+    }
+
+    #[test]
+    fn report_stuck() {
+        let mut puzzle = Sudoku::new(Vec::new()).unwrap();
+        let trace = LogicalSolver::new().solve(&mut puzzle);
+        assert_eq!(trace.status, Status::Stuck);
+        assert!(trace.steps.is_empty());
+        assert_eq!(trace.candidates.len(), 81 * 9);
+        assert_eq!(puzzle, Sudoku::new(Vec::new()).unwrap());
+    }
+
+    #[test]
+    fn report_contradiction() {
+        let mut puzzle = Sudoku::new(boards::UNSOLVABLE_PUZZLE.to_vec()).unwrap();
+        let trace = LogicalSolver::new().solve(&mut puzzle);
+        assert_eq!(trace.status, Status::Contradiction);
+    }
+
+    #[test]
+    fn display_deduction() {
+        let deduction = Deduction {
+            technique: Technique::HiddenSingle,
+            units: vec![Unit::Row(0)],
+            cells: vec![(0, 2)],
+            fins: Vec::new(),
+            digits: Digits::single(4),
+            placements: vec![(0, 2, 4)],
+            eliminations: vec![(1, 2, 5)],
+        };
+        assert_eq!(
+            deduction.to_string(),
+            "Hidden Single: (0, 2) = 4; (1, 2) <> 5;"
+        );
+    }
+}
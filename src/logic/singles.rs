@@ -0,0 +1,79 @@
+use crate::logic::{Deduction, Technique};
+use crate::{Candidates, Digits, Unit, COLS, ROWS};
+
+/// Find a cell with exactly one candidate, which must hold that value.
+pub(super) fn naked_single(candidates: &Candidates) -> Option<Deduction> {
+    for row in 0..ROWS {
+        for col in 0..COLS {
+            let digits = candidates.get(row, col);
+            if digits.len() == 1 {
+                let val = digits.first().unwrap();
+                return Some(Deduction {
+                    technique: Technique::NakedSingle,
+                    units: Vec::new(),
+                    cells: vec![(row, col)],
+                    fins: Vec::new(),
+                    digits,
+                    placements: vec![(row, col, val)],
+                    eliminations: Vec::new(),
+                });
+            }
+        }
+    }
+    None
+}
+
+/// Find a value with exactly one possible cell in a unit, which must hold that value.
+pub(super) fn hidden_single(candidates: &Candidates) -> Option<Deduction> {
+    for unit in Unit::all() {
+        for val in candidates.unit_digits(unit) {
+            let mut places = candidates.cells_with(unit, val);
+            if let (Some((row, col)), None) = (places.next(), places.next()) {
+                return Some(Deduction {
+                    technique: Technique::HiddenSingle,
+                    units: vec![unit],
+                    cells: vec![(row, col)],
+                    fins: Vec::new(),
+                    digits: Digits::single(val),
+                    placements: vec![(row, col, val)],
+                    eliminations: Vec::new(),
+                });
+            }
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{boards, Sudoku};
+
+    #[test]
+    fn find_naked_single() {
+        let mut initial = boards::VALID_SOLUTION.to_vec();
+        initial.retain(|&(row, col, _)| (row, col) != (4, 4));
+        let puzzle = Sudoku::new(initial).unwrap();
+        let deduction = naked_single(&Candidates::new(&puzzle)).unwrap();
+        let val = boards::VALID_SOLUTION
+            .iter()
+            .find(|&&(row, col, _)| (row, col) == (4, 4))
+            .unwrap()
+            .2;
+        assert_eq!(deduction.placements, vec![(4, 4, val)]);
+        assert_eq!(deduction.cells, vec![(4, 4)]);
+    }
+
+    #[test]
+    fn find_hidden_single() {
+        // Four 1s leave the first row and the top-left cage a single place for a 1,
+        // while the cell itself still has other candidates.
+        let puzzle = Sudoku::new(vec![(1, 3, 1), (2, 6, 1), (3, 1, 1), (6, 2, 1)]).unwrap();
+        let candidates = Candidates::new(&puzzle);
+        assert!(naked_single(&candidates).is_none());
+        let deduction = hidden_single(&candidates).unwrap();
+        assert_eq!(deduction.technique, Technique::HiddenSingle);
+        assert_eq!(deduction.units, vec![Unit::Row(0)]);
+        assert_eq!(deduction.placements, vec![(0, 0, 1)]);
+    }
+}
@@ -0,0 +1,164 @@
+use crate::logic::{subsets_, Deduction, Technique};
+use crate::{Candidates, Digits, Unit};
+
+/// Find `size` cells in a unit whose candidates together hold only `size` values.
+/// Those values must go in those cells, so they are eliminated from the rest of the unit.
+pub(super) fn naked_subset(
+    candidates: &Candidates,
+    technique: Technique,
+    size: usize,
+) -> Option<Deduction> {
+    for unit in Unit::all() {
+        let cells = unit.cells();
+        // Cells with a single candidate are left to the naked single.
+        let open: Vec<_> = cells
+            .iter()
+            .copied()
+            .filter(|&(row, col)| {
+                let len = candidates.get(row, col).len();
+                len >= 2 && len <= size
+            })
+            .collect();
+        for subset in subsets_(open.len(), size) {
+            let members: Vec<_> = subset.iter().map(|&i| open[i]).collect();
+            let digits = members.iter().fold(Digits::none(), |digits, &(row, col)| {
+                digits | candidates.get(row, col)
+            });
+            if digits.len() != size {
+                continue;
+            }
+            let mut eliminations = Vec::new();
+            for &(row, col) in cells.iter().filter(|cell| !members.contains(cell)) {
+                for val in candidates.get(row, col) & digits {
+                    eliminations.push((row, col, val));
+                }
+            }
+            if !eliminations.is_empty() {
+                return Some(Deduction {
+                    technique,
+                    units: vec![unit],
+                    cells: members,
+                    fins: Vec::new(),
+                    digits,
+                    placements: Vec::new(),
+                    eliminations,
+                });
+            }
+        }
+    }
+    None
+}
+
+/// Find `size` values in a unit that together fit in only `size` cells.
+/// Those cells must hold those values, so every other candidate is eliminated from them.
+pub(super) fn hidden_subset(
+    candidates: &Candidates,
+    technique: Technique,
+    size: usize,
+) -> Option<Deduction> {
+    for unit in Unit::all() {
+        let cells = unit.cells();
+        let values: Vec<_> = candidates.unit_digits(unit).iter().collect();
+        for subset in subsets_(values.len(), size) {
+            let digits: Digits = subset.iter().map(|&i| values[i]).collect();
+            let members: Vec<_> = cells
+                .iter()
+                .copied()
+                .filter(|&(row, col)| !(candidates.get(row, col) & digits).is_empty())
+                .collect();
+            if members.len() != size {
+                continue;
+            }
+            let mut eliminations = Vec::new();
+            for &(row, col) in &members {
+                for val in candidates.get(row, col) - digits {
+                    eliminations.push((row, col, val));
+                }
+            }
+            if !eliminations.is_empty() {
+                return Some(Deduction {
+                    technique,
+                    units: vec![unit],
+                    cells: members,
+                    fins: Vec::new(),
+                    digits,
+                    placements: Vec::new(),
+                    eliminations,
+                });
+            }
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::logic::tests::narrowed;
+    use crate::COLS;
+
+    /// Return the eliminations that leave only the given values in the given cells.
+    fn keep(cells: &[((usize, usize), &[u8])]) -> Vec<(usize, usize, u8)> {
+        let mut eliminations = Vec::new();
+        for &((row, col), keep) in cells {
+            for val in (1..10).filter(|val| !keep.contains(val)) {
+                eliminations.push((row, col, val));
+            }
+        }
+        eliminations
+    }
+
+    #[test]
+    fn find_naked_pair() {
+        let candidates = narrowed(keep(&[((0, 0), &[1, 2]), ((0, 5), &[1, 2])]));
+        let deduction = naked_subset(&candidates, Technique::NakedPair, 2).unwrap();
+        assert_eq!(deduction.units, vec![Unit::Row(0)]);
+        assert_eq!(deduction.cells, vec![(0, 0), (0, 5)]);
+        assert_eq!(deduction.digits, vec![1, 2].into_iter().collect());
+        assert_eq!(deduction.eliminations.len(), 14);
+        assert!(deduction.eliminations.contains(&(0, 8, 2)));
+        assert!(hidden_subset(&candidates, Technique::HiddenPair, 2).is_none());
+    }
+
+    #[test]
+    fn find_naked_triple() {
+        let candidates = narrowed(keep(&[
+            ((3, 0), &[4, 7]),
+            ((4, 0), &[7, 9]),
+            ((8, 0), &[4, 9]),
+        ]));
+        assert!(naked_subset(&candidates, Technique::NakedPair, 2).is_none());
+        let deduction = naked_subset(&candidates, Technique::NakedTriple, 3).unwrap();
+        assert_eq!(deduction.units, vec![Unit::Col(0)]);
+        assert_eq!(deduction.cells, vec![(3, 0), (4, 0), (8, 0)]);
+        assert_eq!(deduction.digits, vec![4, 7, 9].into_iter().collect());
+        assert_eq!(deduction.eliminations.len(), 18);
+    }
+
+    #[test]
+    fn find_hidden_pair() {
+        let candidates = narrowed((2..COLS).flat_map(|col| vec![(0, col, 1), (0, col, 2)]));
+        assert!(naked_subset(&candidates, Technique::NakedPair, 2).is_none());
+        let deduction = hidden_subset(&candidates, Technique::HiddenPair, 2).unwrap();
+        assert_eq!(deduction.units, vec![Unit::Row(0)]);
+        assert_eq!(deduction.cells, vec![(0, 0), (0, 1)]);
+        assert_eq!(deduction.digits, vec![1, 2].into_iter().collect());
+        assert_eq!(deduction.eliminations.len(), 14);
+        assert!(deduction.eliminations.contains(&(0, 1, 9)));
+    }
+
+    #[test]
+    fn find_hidden_quad() {
+        let cage = Unit::Cage(1, 1).cells();
+        let candidates = narrowed(
+            cage[4..]
+                .iter()
+                .flat_map(|&(row, col)| (1..5).map(move |val| (row, col, val))),
+        );
+        assert!(hidden_subset(&candidates, Technique::HiddenTriple, 3).is_none());
+        let deduction = hidden_subset(&candidates, Technique::HiddenQuad, 4).unwrap();
+        assert_eq!(deduction.units, vec![Unit::Cage(1, 1)]);
+        assert_eq!(deduction.cells, cage[..4].to_vec());
+        assert_eq!(deduction.eliminations.len(), 20);
+    }
+}
@@ -0,0 +1,125 @@
+use std::str::FromStr;
+
+use crate::{Sudoku, SudokuError, COLS, ROWS};
+
+/// Characters accepted as an empty cell when parsing the line format.
+const BLANKS: [char; 5] = ['.', '0', '-', '_', '*'];
+
+impl Sudoku {
+    /// Return the board in the standard 81-character line format.
+    /// Cells are listed in row-major order, with '.' for empty cells.
+    pub fn to_line(&self) -> String {
+        self.cells()
+            .map(|(_, _, val)| match val {
+                Some(val) => (b'0' + val) as char,
+                None => '.',
+            })
+            .collect()
+    }
+}
+
+/// Return the values of a board in the standard 81-character line format, as accepted by
+/// `Sudoku::new`, without checking them against each other.
+pub(crate) fn parse_values_(s: &str) -> Result<Vec<(usize, usize, u8)>, SudokuError> {
+    let mut initial = Vec::new();
+    let mut index = 0;
+    for (offset, ch) in s.chars().enumerate() {
+        if ch.is_whitespace() {
+            continue;
+        }
+        if index >= ROWS * COLS {
+            return Err(SudokuError::InvalidLength {
+                len: index
+                    + s.chars()
+                        .skip(offset)
+                        .filter(|c| !c.is_whitespace())
+                        .count(),
+            });
+        }
+        match ch {
+            '1'..='9' => initial.push((index / COLS, index % COLS, ch as u8 - b'0')),
+            _ if BLANKS.contains(&ch) => {}
+            _ => return Err(SudokuError::InvalidCharacter { offset, found: ch }),
+        }
+        index += 1;
+    }
+    if index != ROWS * COLS {
+        return Err(SudokuError::InvalidLength { len: index });
+    }
+    Ok(initial)
+}
+
+impl FromStr for Sudoku {
+    type Err = SudokuError;
+
+    /// Parse a board from the standard 81-character line format.
+    /// Digits 1 through 9 are givens, and any of '.', '0', '-', '_' or '*' is an empty cell.
+    /// Whitespace is ignored, so grids split over several lines are accepted as well.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Sudoku::new(parse_values_(s)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::boards;
+
+    const PUZZLE_1: &str =
+        ".1.3.....5...6.....4.....28..67....3..2...9..7....84..39.....6.....4...9.....1.5.";
+
+    #[test]
+    fn parse_line() {
+        let puzzle: Sudoku = PUZZLE_1.parse().unwrap();
+        assert_eq!(
+            puzzle,
+            Sudoku::new(boards::VALID_PUZZLE_1.to_vec()).unwrap()
+        );
+    }
+
+    #[test]
+    fn round_trip_line() {
+        let puzzle = Sudoku::new(boards::VALID_PUZZLE_2.to_vec()).unwrap();
+        let line = puzzle.to_line();
+        assert_eq!(line.len(), 81);
+        assert_eq!(line.parse::<Sudoku>().unwrap(), puzzle);
+    }
+
+    #[test]
+    fn parse_with_whitespace_and_blanks() {
+        let grid = PUZZLE_1
+            .replace('.', "0")
+            .chars()
+            .collect::<Vec<_>>()
+            .chunks(9)
+            .map(|row| row.iter().collect::<String>())
+            .collect::<Vec<_>>()
+            .join("\n");
+        let puzzle: Sudoku = grid.parse().unwrap();
+        assert_eq!(puzzle.to_line(), PUZZLE_1);
+    }
+
+    #[test]
+    fn parse_invalid_character() {
+        let line = format!("{}x{}", &PUZZLE_1[..10], &PUZZLE_1[11..]);
+        assert_eq!(
+            line.parse::<Sudoku>(),
+            Err(SudokuError::InvalidCharacter {
+                offset: 10,
+                found: 'x'
+            })
+        );
+    }
+
+    #[test]
+    fn parse_invalid_length() {
+        assert_eq!(
+            PUZZLE_1[1..].parse::<Sudoku>(),
+            Err(SudokuError::InvalidLength { len: 80 })
+        );
+        assert_eq!(
+            format!("{}..", PUZZLE_1).parse::<Sudoku>(),
+            Err(SudokuError::InvalidLength { len: 83 })
+        );
+    }
+}
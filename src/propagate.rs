@@ -0,0 +1,154 @@
+use crate::solver::{run_, Tracker};
+use crate::{Budget, CellSelector, MinimumRemaining, Sudoku, SudokuError, Unit};
+
+impl Sudoku {
+    /// Solve the sudoku board with backtracking, filling every naked and hidden single
+    /// before each guess. Return an Ok if successful.
+    /// If the board cannot be solved, return `SudokuError::Unsolvable`.
+    /// This function mutates the internal board representation in-place.
+    pub fn solve_propagating(&mut self) -> Result<(), SudokuError> {
+        run_(&Budget::unlimited(), |tracker| {
+            self.solve_propagating_(tracker)
+        })
+        .result
+    }
+
+    /// Solve the sudoku board with propagation, recording the work done in `tracker`.
+    pub(crate) fn solve_propagating_(&mut self, tracker: &mut Tracker) -> Result<(), SudokuError> {
+        if self.consistent_() && self.search_propagating_(0, tracker) {
+            Ok(())
+        } else {
+            Err(SudokuError::Unsolvable)
+        }
+    }
+
+    /// Propagate singles, then branch on the cell with the fewest candidates.
+    /// Every placement made at this level is undone if no solution is found below it,
+    /// or if the budget of `tracker` runs out.
+    fn search_propagating_(&mut self, depth: usize, tracker: &mut Tracker) -> bool {
+        if !tracker.enter_(depth) {
+            return false;
+        }
+        let mut trail = Vec::new();
+        let consistent = self.propagate_(&mut trail);
+        tracker.stats.placements += trail.len() as u64;
+        if consistent {
+            let (row, col) = match MinimumRemaining.select(self) {
+                Some(cell) => cell,
+                None => return true,
+            };
+            let candidates = self.candidates(row, col);
+            for val in candidates {
+                self.place_(row, col, val);
+                tracker.stats.placements += 1;
+                if candidates.len() > 1 {
+                    tracker.stats.guesses += 1;
+                }
+                if self.search_propagating_(depth + 1, tracker) {
+                    return true;
+                }
+                self.remove_(row, col);
+                tracker.stats.backtracks += 1;
+                if tracker.exceeded_() {
+                    break;
+                }
+            }
+        }
+        tracker.stats.backtracks += trail.len() as u64;
+        for (row, col) in trail.into_iter().rev() {
+            self.remove_(row, col);
+        }
+        false
+    }
+
+    /// Repeatedly fill naked singles (cells with one candidate) and hidden singles
+    /// (values with one possible cell in a unit) until neither remains.
+    /// Each filled cell is pushed onto `trail` so the caller can undo it.
+    /// Return false as soon as a contradiction is found: an empty cell with no
+    /// candidates, or a unit with no place left for a missing value.
+    pub(crate) fn propagate_(&mut self, trail: &mut Vec<(usize, usize)>) -> bool {
+        loop {
+            let mut progress = false;
+
+            for (row, col) in self.open_cells() {
+                let candidates = self.candidates(row, col);
+                match candidates.len() {
+                    0 => return false,
+                    1 => {
+                        self.place_(row, col, candidates.first().unwrap());
+                        trail.push((row, col));
+                        progress = true;
+                    }
+                    _ => {}
+                }
+            }
+
+            for unit in Unit::all() {
+                for val in !self.unit_digits_(unit) {
+                    let cells = unit.cells();
+                    let mut places = cells
+                        .iter()
+                        .filter(|&&(row, col)| self.candidates(row, col).contains(val));
+                    let (row, col) = match (places.next(), places.next()) {
+                        (None, _) => return false,
+                        (Some(&cell), None) => cell,
+                        _ => continue,
+                    };
+                    self.place_(row, col, val);
+                    trail.push((row, col));
+                    progress = true;
+                }
+            }
+
+            if !progress {
+                return true;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{boards, Method};
+
+    #[test]
+    fn propagate_easy_puzzle() {
+        let mut puzzle = Sudoku::new(boards::EASY_PUZZLE.to_vec()).unwrap();
+        let mut trail = Vec::new();
+        assert!(puzzle.propagate_(&mut trail));
+        assert_eq!(trail.len(), 27);
+        assert!(puzzle.verify());
+    }
+
+    #[test]
+    fn propagate_contradiction() {
+        let mut puzzle = Sudoku::new(boards::UNSOLVABLE_PUZZLE.to_vec()).unwrap();
+        assert!(!puzzle.propagate_(&mut Vec::new()));
+    }
+
+    #[test]
+    fn solve_propagating_matches_backtracking() {
+        for initial in [
+            boards::VALID_PUZZLE_1,
+            boards::VALID_PUZZLE_2,
+            boards::VALID_PUZZLE_3,
+        ]
+        .iter()
+        {
+            let mut expected = Sudoku::new(initial.to_vec()).unwrap();
+            expected.solve_using(Method::Backtracking).unwrap();
+            let mut puzzle = Sudoku::new(initial.to_vec()).unwrap();
+            puzzle.solve_using(Method::Propagation).unwrap();
+            assert_eq!(puzzle, expected);
+        }
+    }
+
+    #[test]
+    fn solve_propagating_unsolvable() {
+        let mut puzzle = Sudoku::new(boards::UNSOLVABLE_PUZZLE.to_vec()).unwrap();
+        let original = puzzle.clone();
+        assert_eq!(puzzle.solve_propagating(), Err(SudokuError::Unsolvable));
+        assert_eq!(puzzle, original);
+    }
+}
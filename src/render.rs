@@ -0,0 +1,132 @@
+use std::fmt::{Display, Error, Formatter};
+
+use crate::{Sudoku, CAGE_COLS, CAGE_ROWS, COLS, ROWS};
+
+/// Layouts available when rendering a board as text.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Style {
+    /// One row per line with spaced digits and '-' for empty cells.
+    Spaced,
+    /// The 81-character line format produced by `Sudoku::to_line`.
+    Compact,
+    /// A box-drawing grid with thick borders around each cage.
+    Grid,
+}
+
+/// A board paired with the style it should be displayed in, created by `Sudoku::render`.
+pub struct Render<'a> {
+    sudoku: &'a Sudoku,
+    style: Style,
+}
+
+impl Sudoku {
+    /// Return a displayable view of the board in the given style.
+    pub fn render(&self, style: Style) -> Render<'_> {
+        Render {
+            sudoku: self,
+            style,
+        }
+    }
+}
+
+impl<'a> Render<'a> {
+    fn fmt_spaced_(&self, fmt: &mut Formatter<'_>) -> Result<(), Error> {
+        for i in 0..ROWS {
+            for j in 0..COLS {
+                match self.sudoku.cell(i, j) {
+                    Some(num) => write!(fmt, " {} ", num)?,
+                    None => write!(fmt, " - ")?,
+                }
+            }
+            writeln!(fmt)?;
+        }
+        Ok(())
+    }
+
+    fn fmt_grid_(&self, fmt: &mut Formatter<'_>) -> Result<(), Error> {
+        Self::fmt_border_(fmt, '┏', '┳', '┓')?;
+        for i in 0..ROWS {
+            if i > 0 && i % CAGE_ROWS == 0 {
+                Self::fmt_border_(fmt, '┣', '╋', '┫')?;
+            }
+            for j in 0..COLS {
+                if j % CAGE_COLS == 0 {
+                    write!(fmt, "┃")?;
+                }
+                match self.sudoku.cell(i, j) {
+                    Some(num) => write!(fmt, " {}", num)?,
+                    None => write!(fmt, " .")?,
+                }
+                if j % CAGE_COLS == CAGE_COLS - 1 {
+                    write!(fmt, " ")?;
+                }
+            }
+            writeln!(fmt, "┃")?;
+        }
+        Self::fmt_border_(fmt, '┗', '┻', '┛')
+    }
+
+    /// Write a horizontal cage border using the given corner and junction characters.
+    fn fmt_border_(
+        fmt: &mut Formatter<'_>,
+        left: char,
+        mid: char,
+        right: char,
+    ) -> Result<(), Error> {
+        write!(fmt, "{}", left)?;
+        for cage_col in 0..COLS / CAGE_COLS {
+            if cage_col > 0 {
+                write!(fmt, "{}", mid)?;
+            }
+            for _ in 0..2 * CAGE_COLS + 1 {
+                write!(fmt, "━")?;
+            }
+        }
+        writeln!(fmt, "{}", right)
+    }
+}
+
+impl<'a> Display for Render<'a> {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> Result<(), Error> {
+        match self.style {
+            Style::Spaced => self.fmt_spaced_(fmt),
+            Style::Compact => write!(fmt, "{}", self.sudoku.to_line()),
+            Style::Grid => self.fmt_grid_(fmt),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::boards;
+
+    #[test]
+    fn render_spaced() {
+        let puzzle = Sudoku::new(boards::VALID_PUZZLE_1.to_vec()).unwrap();
+        let text = format!("{}", puzzle);
+        let lines: Vec<_> = text.lines().collect();
+        assert_eq!(lines.len(), 9);
+        assert_eq!(lines[0], " -  1  -  3  -  -  -  -  - ");
+        assert_eq!(text, puzzle.render(Style::Spaced).to_string());
+    }
+
+    #[test]
+    fn render_compact() {
+        let puzzle = Sudoku::new(boards::VALID_PUZZLE_1.to_vec()).unwrap();
+        assert_eq!(puzzle.render(Style::Compact).to_string(), puzzle.to_line());
+    }
+
+    #[test]
+    fn render_grid() {
+        let puzzle = Sudoku::new(boards::VALID_PUZZLE_1.to_vec()).unwrap();
+        let text = format!("{:#}", puzzle);
+        let lines: Vec<_> = text.lines().collect();
+        assert_eq!(lines.len(), 13);
+        assert_eq!(lines[0], "┏━━━━━━━┳━━━━━━━┳━━━━━━━┓");
+        assert_eq!(lines[1], "┃ . 1 . ┃ 3 . . ┃ . . . ┃");
+        assert_eq!(lines[4], "┣━━━━━━━╋━━━━━━━╋━━━━━━━┫");
+        assert_eq!(lines[12], "┗━━━━━━━┻━━━━━━━┻━━━━━━━┛");
+        assert_eq!(text, puzzle.render(Style::Grid).to_string());
+    }
+}
@@ -0,0 +1,579 @@
+use std::time::{Duration, Instant};
+
+use crate::notation::parse_values_;
+use crate::{Budget, CellSelector, Digits, Origin, SolveStats, Sudoku, SudokuError, COLS, ROWS};
+
+/// First line of a serialized search state.
+const HEADER: &str = "sudoku-search 2";
+
+/// A cell being filled by the search.
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Frame {
+    row: usize,
+    col: usize,
+    // Values that could be placed when the cell was selected.
+    candidates: Digits,
+    // Values that have not been tried yet.
+    remaining: Digits,
+    // Value currently placed in the cell by the search, if any.
+    placed: Option<u8>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum State {
+    Start,
+    Running,
+    Solved,
+    Exhausted,
+}
+
+/// The effect of a single call to `Search::step`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Step {
+    /// A value was placed in a cell, and the search moved on to the next empty cell.
+    Placed { row: usize, col: usize, val: u8 },
+    /// Every value was tried in a cell without success, so the search stepped back.
+    Backtracked { row: usize, col: usize },
+    /// Every cell is filled. Stepping again resumes the search for another solution.
+    Solved,
+    /// The search space is exhausted and there are no more solutions.
+    Exhausted,
+}
+
+/// The backtracking search from `Sudoku::solve`, with the recursion replaced by an
+/// explicit stack. The search can be advanced one step at a time, paused by running it
+/// within a `Budget`, saved to a string and restored later.
+/// Since there is no recursion, deep searches cannot overflow the call stack.
+#[derive(Clone, Debug)]
+pub struct Search<S> {
+    puzzle: Sudoku,
+    sudoku: Sudoku,
+    selector: S,
+    stack: Vec<Frame>,
+    state: State,
+    stats: SolveStats,
+}
+
+impl<S: CellSelector> Search<S> {
+    /// Create a search over the given board, using the given cell selection strategy.
+    pub fn new(sudoku: &Sudoku, selector: S) -> Self {
+        Self {
+            puzzle: sudoku.clone(),
+            sudoku: sudoku.clone(),
+            selector,
+            stack: Vec::new(),
+            state: State::Start,
+            stats: SolveStats::default(),
+        }
+    }
+
+    /// Return the board in its current, possibly partially filled, state.
+    pub fn sudoku(&self) -> &Sudoku {
+        &self.sudoku
+    }
+
+    /// Return statistics describing the work done so far.
+    pub fn stats(&self) -> SolveStats {
+        self.stats
+    }
+
+    /// Advance the search by placing or backtracking a single value.
+    pub fn step(&mut self) -> Step {
+        match self.state {
+            State::Exhausted => return Step::Exhausted,
+            State::Start => {
+                self.stats.nodes += 1;
+                if !self.sudoku.consistent_() {
+                    self.state = State::Exhausted;
+                    return Step::Exhausted;
+                }
+                if !self.select_() {
+                    self.state = State::Solved;
+                    return Step::Solved;
+                }
+            }
+            State::Running | State::Solved => {}
+        }
+        self.state = State::Running;
+
+        let frame = match self.stack.last_mut() {
+            Some(frame) => frame,
+            None => {
+                self.state = State::Exhausted;
+                return Step::Exhausted;
+            }
+        };
+        let (row, col) = (frame.row, frame.col);
+        if frame.placed.take().is_some() {
+            self.sudoku.remove_(row, col);
+            self.stats.backtracks += 1;
+        }
+        let val = match frame.remaining.first() {
+            Some(val) => val,
+            None => {
+                self.stack.pop();
+                return Step::Backtracked { row, col };
+            }
+        };
+        frame.remaining.remove(val);
+        frame.placed = Some(val);
+        if frame.candidates.len() > 1 {
+            self.stats.guesses += 1;
+        }
+        self.sudoku.place_(row, col, val);
+        self.stats.placements += 1;
+        self.stats.nodes += 1;
+        self.stats.max_depth = self.stats.max_depth.max(self.stack.len());
+
+        if self.select_() {
+            Step::Placed { row, col, val }
+        } else {
+            self.state = State::Solved;
+            Step::Solved
+        }
+    }
+
+    /// Step the search until a solution is found, the search is exhausted or the budget
+    /// runs out. Return `SudokuError::Unsolvable` or `SudokuError::BudgetExceeded`
+    /// respectively in the latter cases. After running out of budget, the search can be
+    /// resumed by calling `run` again.
+    pub fn run(&mut self, budget: &Budget) -> Result<(), SudokuError> {
+        let start = Instant::now();
+        let start_nodes = self.stats.nodes;
+        let result = loop {
+            if budget.exhausted_(self.stats.nodes - start_nodes) {
+                break Err(SudokuError::BudgetExceeded);
+            }
+            match self.step() {
+                Step::Solved => break Ok(()),
+                Step::Exhausted => break Err(SudokuError::Unsolvable),
+                Step::Placed { .. } | Step::Backtracked { .. } => {}
+            }
+        };
+        self.stats.elapsed += start.elapsed();
+        result
+    }
+
+    /// Select the next cell to fill and push it onto the stack.
+    /// Return false if the board is complete.
+    fn select_(&mut self) -> bool {
+        match self.selector.select(&self.sudoku) {
+            Some((row, col)) => {
+                let candidates = self.sudoku.candidates(row, col);
+                self.stack.push(Frame {
+                    row,
+                    col,
+                    candidates,
+                    remaining: candidates,
+                    placed: None,
+                });
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Return the search state as text, which `Search::restore` turns back into an
+    /// equivalent search. The selector is not saved.
+    /// The puzzle line holds the givens only. Every other value on the starting board is
+    /// listed on its own line along with its origin.
+    pub fn save(&self) -> String {
+        let state = match self.state {
+            State::Start => "start",
+            State::Running => "running",
+            State::Solved => "solved",
+            State::Exhausted => "exhausted",
+        };
+        let stats = self.stats;
+        let mut text = format!(
+            "{}\npuzzle {}\nstate {}\nstats {} {} {} {} {} {}\n",
+            HEADER,
+            self.puzzle
+                .cells()
+                .map(|(row, col, val)| match val {
+                    Some(val) if self.puzzle.is_given(row, col) => (b'0' + val) as char,
+                    _ => '.',
+                })
+                .collect::<String>(),
+            state,
+            stats.nodes,
+            stats.max_depth,
+            stats.placements,
+            stats.guesses,
+            stats.backtracks,
+            stats.elapsed.as_nanos()
+        );
+        for (row, col, val) in self.puzzle.cells() {
+            let key = match self.puzzle.origin(row, col) {
+                Some(Origin::Entered) => "entered",
+                Some(Origin::Solved) => "solved",
+                Some(Origin::Given) | None => continue,
+            };
+            let val = val.unwrap();
+            text.push_str(&format!("{} {} {} {}\n", key, row, col, val));
+        }
+        for frame in &self.stack {
+            text.push_str(&format!(
+                "frame {} {} {} {} {}\n",
+                frame.row,
+                frame.col,
+                digits_to_string(frame.candidates),
+                digits_to_string(frame.remaining),
+                frame.placed.map_or('-', |val| (b'0' + val) as char)
+            ));
+        }
+        text
+    }
+
+    /// Restore a search saved with `Search::save`, continuing with the given selector.
+    /// If the text is malformed, return `SudokuError::InvalidSearchState` with the
+    /// 1-based number of the offending line.
+    pub fn restore(text: &str, selector: S) -> Result<Self, SudokuError> {
+        let mut lines = text.lines().enumerate().map(|(i, line)| (i + 1, line));
+        let invalid = |line: usize| SudokuError::InvalidSearchState { line };
+
+        match lines.next() {
+            Some((_, HEADER)) => {}
+            _ => return Err(invalid(1)),
+        }
+        let (n, line) = lines.next().ok_or_else(|| invalid(2))?;
+        // The board may hold repeated values entered with `Check::Allow`, so it is rebuilt
+        // without checking them against each other.
+        let mut puzzle = field(line, "puzzle")
+            .and_then(|line| parse_values_(line).ok())
+            .and_then(|initial| Sudoku::from_values_(initial).ok())
+            .ok_or_else(|| invalid(n))?;
+        let (state_line, line) = lines.next().ok_or_else(|| invalid(3))?;
+        let state = match field(line, "state") {
+            Some("start") => State::Start,
+            Some("running") => State::Running,
+            Some("solved") => State::Solved,
+            Some("exhausted") => State::Exhausted,
+            _ => return Err(invalid(state_line)),
+        };
+        let (n, line) = lines.next().ok_or_else(|| invalid(4))?;
+        let numbers: Vec<u128> = field(line, "stats")
+            .map(|line| line.split(' ').map(|x| x.parse().ok()).collect())
+            .and_then(|numbers: Option<Vec<u128>>| numbers)
+            .filter(|numbers| numbers.len() == 6)
+            .ok_or_else(|| invalid(n))?;
+        let stats = SolveStats {
+            nodes: numbers[0] as u64,
+            max_depth: numbers[1] as usize,
+            placements: numbers[2] as u64,
+            guesses: numbers[3] as u64,
+            backtracks: numbers[4] as u64,
+            elapsed: Duration::from_nanos(numbers[5] as u64),
+        };
+
+        let mut lines = lines.peekable();
+        while let Some(&(n, line)) = lines.peek() {
+            let (cell, entered) = match (field(line, "entered"), field(line, "solved")) {
+                (Some(cell), _) => (cell, true),
+                (_, Some(cell)) => (cell, false),
+                _ => break,
+            };
+            let (row, col, val) = parse_cell(cell).ok_or_else(|| invalid(n))?;
+            if puzzle.cell(row, col).is_some() {
+                return Err(invalid(n));
+            }
+            if entered {
+                puzzle.enter_(row, col, val);
+            } else {
+                puzzle.place_(row, col, val);
+            }
+            lines.next();
+        }
+
+        let mut sudoku = puzzle.clone();
+        let mut stack = Vec::new();
+        for (n, line) in lines {
+            let frame = field(line, "frame")
+                .and_then(parse_frame)
+                .ok_or_else(|| invalid(n))?;
+            // Only the top frame may be waiting for its first value. Each frame holds the
+            // candidates of its cell on the board below it, and values are tried in
+            // ascending order, so the remaining values are those above the placed one.
+            if matches!(stack.last(), Some(Frame { placed: None, .. }))
+                || sudoku.cell(frame.row, frame.col).is_some()
+                || sudoku.candidates(frame.row, frame.col) != frame.candidates
+            {
+                return Err(invalid(n));
+            }
+            let untried = match frame.placed {
+                Some(val) => frame.candidates.iter().filter(|&d| d > val).collect(),
+                None => frame.candidates,
+            };
+            if frame.remaining != untried {
+                return Err(invalid(n));
+            }
+            if let Some(val) = frame.placed {
+                if !frame.candidates.contains(val) {
+                    return Err(invalid(n));
+                }
+                sudoku.place_(frame.row, frame.col, val);
+            }
+            stack.push(frame);
+        }
+
+        let complete = sudoku.open_cells().next().is_none();
+        let all_placed = stack.iter().all(|frame| frame.placed.is_some());
+        let consistent = match state {
+            State::Start | State::Exhausted => stack.is_empty(),
+            State::Running => !complete,
+            State::Solved => complete && all_placed,
+        };
+        if !consistent {
+            return Err(invalid(state_line));
+        }
+
+        Ok(Self {
+            puzzle,
+            sudoku,
+            selector,
+            stack,
+            state,
+            stats,
+        })
+    }
+}
+
+/// Return the rest of `line` if it starts with the given key followed by a space.
+fn field<'a>(line: &'a str, key: &str) -> Option<&'a str> {
+    line.strip_prefix(key)?.strip_prefix(' ')
+}
+
+/// Return the digits of a set written out in increasing order, or "-" for the empty set.
+fn digits_to_string(digits: Digits) -> String {
+    if digits.is_empty() {
+        return "-".to_string();
+    }
+    digits.iter().map(|val| (b'0' + val) as char).collect()
+}
+
+/// Parse a set of digits written by `digits_to_string`.
+fn parse_digits(text: &str) -> Option<Digits> {
+    if text == "-" {
+        return Some(Digits::none());
+    }
+    text.chars()
+        .map(|ch| match ch {
+            '1'..='9' => Some(ch as u8 - b'0'),
+            _ => None,
+        })
+        .collect::<Option<Vec<u8>>>()
+        .map(|vals| vals.into_iter().collect())
+}
+
+/// Parse the row, column and value of a cell line written by `Search::save`.
+fn parse_cell(text: &str) -> Option<(usize, usize, u8)> {
+    let parts: Vec<&str> = text.split(' ').collect();
+    if parts.len() != 3 {
+        return None;
+    }
+    let row: usize = parts[0].parse().ok()?;
+    let col: usize = parts[1].parse().ok()?;
+    let val = parse_digits(parts[2])?;
+    if row >= ROWS || col >= COLS || val.len() != 1 {
+        return None;
+    }
+    Some((row, col, val.first()?))
+}
+
+/// Parse the fields of a frame line written by `Search::save`.
+fn parse_frame(text: &str) -> Option<Frame> {
+    let parts: Vec<&str> = text.split(' ').collect();
+    if parts.len() != 5 {
+        return None;
+    }
+    let row: usize = parts[0].parse().ok()?;
+    let col: usize = parts[1].parse().ok()?;
+    let candidates = parse_digits(parts[2])?;
+    let remaining = parse_digits(parts[3])?;
+    let placed = match parts[4] {
+        "-" => None,
+        val => Some(parse_digits(val)?.first()?),
+    };
+    if row >= ROWS || col >= COLS || remaining - candidates != Digits::none() {
+        return None;
+    }
+    Some(Frame {
+        row,
+        col,
+        candidates,
+        remaining,
+        placed,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{boards, Check, FirstOpen, MinimumRemaining};
+
+    #[test]
+    fn run_matches_recursive_search() {
+        for initial in [
+            boards::VALID_PUZZLE_1,
+            boards::VALID_PUZZLE_2,
+            boards::VALID_PUZZLE_3,
+        ]
+        .iter()
+        {
+            let mut expected = Sudoku::new(initial.to_vec()).unwrap();
+            let stats = expected.solve_with_stats().unwrap();
+            let puzzle = Sudoku::new(initial.to_vec()).unwrap();
+            let mut search = Search::new(&puzzle, MinimumRemaining);
+            search.run(&Budget::unlimited()).unwrap();
+            assert_eq!(search.sudoku(), &expected);
+            let iterative = SolveStats {
+                elapsed: stats.elapsed,
+                ..search.stats()
+            };
+            assert_eq!(iterative, stats);
+        }
+    }
+
+    #[test]
+    fn step_through_search() {
+        let mut initial = boards::VALID_SOLUTION.to_vec();
+        initial.retain(|&(row, col, _)| (row, col) != (0, 0) && (row, col) != (8, 8));
+        let puzzle = Sudoku::new(initial).unwrap();
+        let mut search = Search::new(&puzzle, FirstOpen);
+        assert_eq!(
+            search.step(),
+            Step::Placed {
+                row: 0,
+                col: 0,
+                val: 1
+            }
+        );
+        assert_eq!(search.step(), Step::Solved);
+        assert!(search.sudoku().verify());
+        assert_eq!(search.step(), Step::Backtracked { row: 8, col: 8 });
+        assert_eq!(search.step(), Step::Backtracked { row: 0, col: 0 });
+        assert_eq!(search.step(), Step::Exhausted);
+        assert_eq!(search.step(), Step::Exhausted);
+    }
+
+    #[test]
+    fn pause_and_resume() {
+        let puzzle = Sudoku::new(boards::VALID_PUZZLE_2.to_vec()).unwrap();
+        let mut expected = Search::new(&puzzle, MinimumRemaining);
+        expected.run(&Budget::unlimited()).unwrap();
+
+        let mut search = Search::new(&puzzle, MinimumRemaining);
+        let budget = Budget::unlimited().max_nodes(100);
+        let mut pauses = 0;
+        while search.run(&budget) == Err(SudokuError::BudgetExceeded) {
+            pauses += 1;
+        }
+        assert!(pauses > 0);
+        assert_eq!(search.sudoku(), expected.sudoku());
+        assert_eq!(search.stats().nodes, expected.stats().nodes);
+    }
+
+    #[test]
+    fn save_and_restore() {
+        let puzzle = Sudoku::new(boards::VALID_PUZZLE_3.to_vec()).unwrap();
+        let mut expected = Search::new(&puzzle, MinimumRemaining);
+        expected.run(&Budget::unlimited()).unwrap();
+
+        let mut search = Search::new(&puzzle, MinimumRemaining);
+        for _ in 0..50 {
+            search.step();
+        }
+        let saved = search.save();
+        let mut restored = Search::restore(&saved, MinimumRemaining).unwrap();
+        assert_eq!(restored.sudoku(), search.sudoku());
+        assert_eq!(restored.save(), saved);
+        restored.run(&Budget::unlimited()).unwrap();
+        assert_eq!(restored.sudoku(), expected.sudoku());
+        assert_eq!(restored.stats().placements, expected.stats().placements);
+    }
+
+    #[test]
+    fn save_and_restore_entered_cells() {
+        let mut puzzle = Sudoku::new(boards::VALID_PUZZLE_1.to_vec()).unwrap();
+        puzzle.set(0, 0, 6).unwrap();
+        let mut search = Search::new(&puzzle, MinimumRemaining);
+        for _ in 0..20 {
+            search.step();
+        }
+        let saved = search.save();
+        assert!(saved.contains("\nentered 0 0 6\n"));
+        let restored = Search::restore(&saved, MinimumRemaining).unwrap();
+        assert_eq!(restored.sudoku(), search.sudoku());
+        assert_eq!(restored.sudoku().origin(0, 0), Some(Origin::Entered));
+        assert_eq!(restored.sudoku().origin(0, 1), Some(Origin::Given));
+        assert_eq!(restored.save(), saved);
+    }
+
+    #[test]
+    fn save_and_restore_conflicting_board() {
+        let mut puzzle = Sudoku::new(boards::VALID_PUZZLE_1.to_vec()).unwrap();
+        puzzle.set_with(0, 0, 1, Check::Allow).unwrap();
+        let mut search = Search::new(&puzzle, FirstOpen);
+        assert_eq!(search.step(), Step::Exhausted);
+        let restored = Search::restore(&search.save(), FirstOpen).unwrap();
+        assert_eq!(restored.sudoku(), &puzzle);
+        assert_eq!(restored.sudoku().conflicts(), puzzle.conflicts());
+        assert_eq!(restored.save(), search.save());
+    }
+
+    #[test]
+    fn restore_invalid_state() {
+        let puzzle = Sudoku::new(boards::VALID_PUZZLE_1.to_vec()).unwrap();
+        let mut search = Search::new(&puzzle, FirstOpen);
+        search.step();
+        let saved = search.save();
+        assert_eq!(
+            Search::restore("", FirstOpen).err(),
+            Some(SudokuError::InvalidSearchState { line: 1 })
+        );
+        let broken = saved.replace("state running", "state paused");
+        assert_eq!(
+            Search::restore(&broken, FirstOpen).err(),
+            Some(SudokuError::InvalidSearchState { line: 3 })
+        );
+        let broken = format!("{}frame 0 1 2 2 -\n", saved);
+        assert_eq!(
+            Search::restore(&broken, FirstOpen).err(),
+            Some(SudokuError::InvalidSearchState { line: 7 })
+        );
+
+        // The search has placed 2 at (0, 0) and selected (0, 2).
+        let frames = "frame 0 0 2689 689 2\nframe 0 2 789 789 -\n";
+        assert!(saved.ends_with(frames));
+        let frames = |text: &str| saved.replace(frames, text);
+        let valid = frames("frame 0 0 2689 89 6\nframe 0 2 789 789 -\n");
+        assert!(Search::restore(&valid, FirstOpen).is_ok());
+        let invalid_frames = [
+            // The placed value is not a candidate.
+            ("frame 0 0 2689 689 1\n", 5),
+            ("frame 0 0 1 - 9\n", 5),
+            // The remaining values are not those above the placed value.
+            ("frame 0 0 2689 689 6\n", 5),
+            ("frame 0 0 2689 - 2\n", 5),
+            ("frame 0 0 2689 89 6\nframe 0 2 789 89 -\n", 6),
+            // The candidates differ from those on the board.
+            ("frame 0 0 689 89 6\n", 5),
+            // Only the top frame may be without a value.
+            ("frame 0 0 2689 2689 -\nframe 0 2 789 789 -\n", 6),
+        ];
+        for &(text, line) in invalid_frames.iter() {
+            assert_eq!(
+                Search::restore(&frames(text), FirstOpen).err(),
+                Some(SudokuError::InvalidSearchState { line })
+            );
+        }
+
+        for state in ["start", "solved", "exhausted"].iter() {
+            let broken = saved.replace("state running", &format!("state {}", state));
+            assert_eq!(
+                Search::restore(&broken, FirstOpen).err(),
+                Some(SudokuError::InvalidSearchState { line: 3 })
+            );
+        }
+    }
+}
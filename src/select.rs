@@ -0,0 +1,69 @@
+use crate::Sudoku;
+
+/// Strategy for choosing which empty cell the backtracking search fills next.
+pub trait CellSelector {
+    /// Return the row and column indexes of the next cell to fill.
+    /// If all cells are filled, return None.
+    fn select(&self, sudoku: &Sudoku) -> Option<(usize, usize)>;
+}
+
+/// Select the first empty cell in row-major order.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct FirstOpen;
+
+impl CellSelector for FirstOpen {
+    fn select(&self, sudoku: &Sudoku) -> Option<(usize, usize)> {
+        sudoku.find_open_cell_()
+    }
+}
+
+/// Select the empty cell with the fewest candidates, known as the minimum remaining
+/// values heuristic. A cell with no candidates is returned immediately, since the
+/// search has to backtrack from it anyway.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct MinimumRemaining;
+
+impl CellSelector for MinimumRemaining {
+    fn select(&self, sudoku: &Sudoku) -> Option<(usize, usize)> {
+        let mut best = None;
+        let mut best_len = usize::MAX;
+        for (row, col) in sudoku.open_cells() {
+            let len = sudoku.candidates(row, col).len();
+            if len == 0 {
+                return Some((row, col));
+            }
+            if len < best_len {
+                best = Some((row, col));
+                best_len = len;
+            }
+        }
+        best
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::boards;
+
+    #[test]
+    fn select_first_open() {
+        let puzzle = Sudoku::new(boards::VALID_PUZZLE_1.to_vec()).unwrap();
+        assert_eq!(FirstOpen.select(&puzzle), Some((0, 0)));
+        let solved = Sudoku::new(boards::VALID_SOLUTION.to_vec()).unwrap();
+        assert_eq!(FirstOpen.select(&solved), None);
+    }
+
+    #[test]
+    fn select_minimum_remaining() {
+        let puzzle = Sudoku::new(boards::VALID_ROW.to_vec()[1..].to_vec()).unwrap();
+        assert_eq!(MinimumRemaining.select(&puzzle), Some((4, 0)));
+    }
+
+    #[test]
+    fn select_dead_end() {
+        let puzzle = Sudoku::new(boards::UNSOLVABLE_PUZZLE.to_vec()).unwrap();
+        let (row, col) = MinimumRemaining.select(&puzzle).unwrap();
+        assert!(puzzle.candidates(row, col).is_empty());
+    }
+}
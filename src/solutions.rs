@@ -0,0 +1,70 @@
+use crate::{FirstOpen, Search, Step, Sudoku};
+
+/// Iterator over every solution of a board, created by `Sudoku::solutions`.
+/// Each call to `next` resumes the underlying `Search` where it left off.
+pub struct Solutions {
+    search: Search<FirstOpen>,
+}
+
+impl Solutions {
+    pub(crate) fn new(sudoku: &Sudoku) -> Self {
+        Self {
+            search: Search::new(sudoku, FirstOpen),
+        }
+    }
+}
+
+impl Iterator for Solutions {
+    type Item = Sudoku;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            match self.search.step() {
+                Step::Solved => return Some(self.search.sudoku().clone()),
+                Step::Exhausted => return None,
+                Step::Placed { .. } | Step::Backtracked { .. } => {}
+            }
+        }
+    }
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{boards, SolutionCount};
+
+    #[test]
+    fn iterate_unique_solution() {
+        let puzzle = Sudoku::new(boards::VALID_PUZZLE_1.to_vec()).unwrap();
+        let mut solved = puzzle.clone();
+        solved.solve().unwrap();
+        let solutions: Vec<_> = puzzle.solutions().collect();
+        assert_eq!(solutions, vec![solved]);
+    }
+
+    #[test]
+    fn iterate_empty_board() {
+        let puzzle = Sudoku::new(Vec::new()).unwrap();
+        let solutions: Vec<_> = puzzle.solutions().take(20).collect();
+        assert_eq!(solutions.len(), 20);
+        for (i, solution) in solutions.iter().enumerate() {
+            assert!(solution.verify());
+            assert!(!solutions[..i].contains(solution));
+        }
+    }
+
+    #[test]
+    fn iterate_matches_count() {
+        let mut initial = boards::VALID_SOLUTION.to_vec();
+        initial.retain(|&(row, _, _)| row > 1);
+        let puzzle = Sudoku::new(initial).unwrap();
+        let count = puzzle.solutions().count();
+        assert_eq!(puzzle.count_solutions(100), SolutionCount::Exact(count));
+    }
+
+    #[test]
+    fn iterate_complete_board() {
+        let puzzle = Sudoku::new(boards::VALID_SOLUTION.to_vec()).unwrap();
+        let solutions: Vec<_> = puzzle.solutions().collect();
+        assert_eq!(solutions, vec![puzzle]);
+    }
+}
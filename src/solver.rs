@@ -0,0 +1,277 @@
+use std::time::{Duration, Instant};
+
+use crate::{Budget, CellSelector, FirstOpen, MinimumRemaining, Search, Sudoku, SudokuError};
+
+/// Statistics describing the work done by a solver.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SolveStats {
+    /// Number of search nodes visited.
+    pub nodes: u64,
+    /// Deepest level of the search tree reached, where the root is level 0.
+    pub max_depth: usize,
+    /// Number of values placed on the board, including ones later undone.
+    pub placements: u64,
+    /// Number of placements made while other values were still possible for the cell.
+    pub guesses: u64,
+    /// Number of placements undone because the search below them failed.
+    pub backtracks: u64,
+    /// Wall-clock time spent solving.
+    pub elapsed: Duration,
+}
+
+/// The result of running a solver, along with statistics about the run.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Outcome {
+    pub result: Result<(), SudokuError>,
+    pub stats: SolveStats,
+}
+
+/// An algorithm that solves a sudoku board in-place.
+pub trait Solver {
+    /// Solve the board, giving up once the budget runs out.
+    /// On success every empty cell is filled in. Otherwise the result is
+    /// `SudokuError::Unsolvable` or `SudokuError::BudgetExceeded`, and the board is
+    /// left unchanged.
+    fn solve_within(&self, sudoku: &mut Sudoku, budget: &Budget) -> Outcome;
+
+    /// Solve the board without any limit on the work done.
+    fn solve(&self, sudoku: &mut Sudoku) -> Outcome {
+        self.solve_within(sudoku, &Budget::unlimited())
+    }
+}
+
+/// Statistics and budget shared by every level of a search.
+pub(crate) struct Tracker<'a> {
+    pub(crate) stats: SolveStats,
+    budget: &'a Budget,
+    exceeded: bool,
+}
+
+impl<'a> Tracker<'a> {
+    pub(crate) fn new(budget: &'a Budget) -> Self {
+        Self {
+            stats: SolveStats::default(),
+            budget,
+            exceeded: false,
+        }
+    }
+
+    /// Record a visit to a search node at the given depth.
+    /// Return false if the budget has run out, in which case the search must unwind.
+    pub(crate) fn enter_(&mut self, depth: usize) -> bool {
+        if self.exceeded || self.budget.exhausted_(self.stats.nodes) {
+            self.exceeded = true;
+            return false;
+        }
+        self.stats.nodes += 1;
+        self.stats.max_depth = self.stats.max_depth.max(depth);
+        true
+    }
+
+    /// Return true iff the budget ran out during the search.
+    pub(crate) fn exceeded_(&self) -> bool {
+        self.exceeded
+    }
+}
+
+/// Run `solve` within the given budget, and time it.
+pub(crate) fn run_<F>(budget: &Budget, solve: F) -> Outcome
+where
+    F: FnOnce(&mut Tracker) -> Result<(), SudokuError>,
+{
+    let start = Instant::now();
+    let mut tracker = Tracker::new(budget);
+    let mut result = solve(&mut tracker);
+    if tracker.exceeded_() {
+        result = Err(SudokuError::BudgetExceeded);
+    }
+    let mut stats = tracker.stats;
+    stats.elapsed = start.elapsed();
+    Outcome { result, stats }
+}
+
+/// Depth-first backtracking, filling cells in the order chosen by a `CellSelector`.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct BacktrackingSolver<S> {
+    selector: S,
+}
+
+impl<S: CellSelector> BacktrackingSolver<S> {
+    /// Create a backtracking solver that uses the given cell selection strategy.
+    pub fn new(selector: S) -> Self {
+        Self { selector }
+    }
+}
+
+impl<S: CellSelector> Solver for BacktrackingSolver<S> {
+    fn solve_within(&self, sudoku: &mut Sudoku, budget: &Budget) -> Outcome {
+        run_(budget, |tracker| {
+            sudoku.solve_with_(&self.selector, tracker)
+        })
+    }
+}
+
+/// The same search as `BacktrackingSolver`, run on an explicit stack with `Search`
+/// instead of recursion.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct IterativeSolver<S> {
+    selector: S,
+}
+
+impl<S: CellSelector + Clone> IterativeSolver<S> {
+    /// Create an iterative solver that uses the given cell selection strategy.
+    pub fn new(selector: S) -> Self {
+        Self { selector }
+    }
+}
+
+impl<S: CellSelector + Clone> Solver for IterativeSolver<S> {
+    fn solve_within(&self, sudoku: &mut Sudoku, budget: &Budget) -> Outcome {
+        let mut search = Search::new(sudoku, self.selector.clone());
+        let result = search.run(budget);
+        if result.is_ok() {
+            *sudoku = search.sudoku().clone();
+        }
+        Outcome {
+            result,
+            stats: search.stats(),
+        }
+    }
+}
+
+/// Backtracking that fills naked and hidden singles before every guess.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct PropagationSolver;
+
+impl Solver for PropagationSolver {
+    fn solve_within(&self, sudoku: &mut Sudoku, budget: &Budget) -> Outcome {
+        run_(budget, |tracker| sudoku.solve_propagating_(tracker))
+    }
+}
+
+/// Knuth's Algorithm X over the exact cover matrix, using Dancing Links.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ExactCoverSolver;
+
+impl Solver for ExactCoverSolver {
+    fn solve_within(&self, sudoku: &mut Sudoku, budget: &Budget) -> Outcome {
+        run_(budget, |tracker| sudoku.solve_exact_cover_(tracker))
+    }
+}
+
+/// Solvers that can be chosen at runtime, for example from a configuration value.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Method {
+    /// Backtracking that fills cells in row-major order, like the original solver.
+    NaiveBacktracking,
+    /// Backtracking that fills the cell with the fewest candidates first, as done by
+    /// `Sudoku::solve`.
+    Backtracking,
+    /// The same search as `Backtracking`, run on an explicit stack instead of recursion.
+    Iterative,
+    /// Backtracking that fills naked and hidden singles before every guess.
+    Propagation,
+    /// Knuth's Algorithm X over the exact cover matrix, using Dancing Links.
+    ExactCover,
+}
+
+impl Method {
+    /// Return every available method.
+    pub fn all() -> [Method; 5] {
+        [
+            Method::NaiveBacktracking,
+            Method::Backtracking,
+            Method::Iterative,
+            Method::Propagation,
+            Method::ExactCover,
+        ]
+    }
+
+    /// Return the solver implementing this method.
+    pub fn solver(self) -> Box<dyn Solver> {
+        match self {
+            Method::NaiveBacktracking => Box::new(BacktrackingSolver::new(FirstOpen)),
+            Method::Backtracking => Box::new(BacktrackingSolver::new(MinimumRemaining)),
+            Method::Iterative => Box::new(IterativeSolver::new(MinimumRemaining)),
+            Method::Propagation => Box::new(PropagationSolver),
+            Method::ExactCover => Box::new(ExactCoverSolver),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::boards;
+
+    #[test]
+    fn solvers_agree() {
+        for initial in [
+            boards::VALID_PUZZLE_1,
+            boards::VALID_PUZZLE_2,
+            boards::VALID_PUZZLE_3,
+        ]
+        .iter()
+        {
+            let mut expected = Sudoku::new(initial.to_vec()).unwrap();
+            expected.solve().unwrap();
+            for method in Method::all().iter() {
+                let mut puzzle = Sudoku::new(initial.to_vec()).unwrap();
+                let outcome = method.solver().solve(&mut puzzle);
+                assert_eq!(outcome.result, Ok(()), "{:?}", method);
+                assert!(outcome.stats.placements > 0, "{:?}", method);
+                assert_eq!(puzzle, expected, "{:?}", method);
+            }
+        }
+    }
+
+    #[test]
+    fn solvers_report_unsolvable() {
+        let original = Sudoku::new(boards::UNSOLVABLE_PUZZLE.to_vec()).unwrap();
+        for method in Method::all().iter() {
+            let mut puzzle = original.clone();
+            let outcome = method.solver().solve(&mut puzzle);
+            assert_eq!(outcome.result, Err(SudokuError::Unsolvable), "{:?}", method);
+            assert_eq!(puzzle, original, "{:?}", method);
+        }
+    }
+
+    #[test]
+    fn backtracking_counts_work() {
+        let mut puzzle = Sudoku::new(boards::VALID_PUZZLE_2.to_vec()).unwrap();
+        let outcome = BacktrackingSolver::new(FirstOpen).solve(&mut puzzle);
+        let stats = outcome.stats;
+        assert_eq!(stats.placements - stats.backtracks, 81 - 22);
+        assert_eq!(stats.max_depth, 81 - 22);
+        assert_eq!(stats.nodes, stats.placements + 1);
+        assert!(stats.guesses > 0 && stats.guesses <= stats.placements);
+    }
+
+    #[test]
+    fn solvers_respect_node_budget() {
+        let original = Sudoku::new(boards::VALID_PUZZLE_2.to_vec()).unwrap();
+        let budget = Budget::unlimited().max_nodes(10);
+        for method in Method::all().iter() {
+            let mut puzzle = original.clone();
+            let outcome = method.solver().solve_within(&mut puzzle, &budget);
+            assert_eq!(
+                outcome.result,
+                Err(SudokuError::BudgetExceeded),
+                "{:?}",
+                method
+            );
+            assert_eq!(outcome.stats.nodes, 10, "{:?}", method);
+            assert_eq!(puzzle, original, "{:?}", method);
+        }
+    }
+
+    #[test]
+    fn propagation_needs_no_guesses() {
+        let mut puzzle = Sudoku::new(boards::EASY_PUZZLE.to_vec()).unwrap();
+        let stats = PropagationSolver.solve(&mut puzzle).stats;
+        assert_eq!(stats.guesses, 0);
+        assert_eq!(stats.backtracks, 0);
+        assert_eq!(stats.nodes, 1);
+        assert_eq!(stats.placements, 27);
+    }
+}
@@ -0,0 +1,1139 @@
+use std::fmt::{Display, Error, Formatter};
+
+use crate::solver::{run_, Tracker};
+use crate::{
+    Budget, CellSelector, Conflict, Digits, Method, MinimumRemaining, Solutions, SolveStats, Style,
+    SudokuError, Unit, CAGE_COLS, CAGE_ROWS, COLS, ROWS,
+};
+
+/// A 9-by-9 sudoku board.
+/// Empty cells are represented as None.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Sudoku {
+    board: Vec<Vec<Option<u8>>>,
+    // Digits present in each row, column and cage, kept in sync by `place_` and `remove_`.
+    // Cages are indexed in row-major order.
+    rows: [Digits; ROWS],
+    cols: [Digits; COLS],
+    cages: [Digits; ROWS],
+    // Bit `row * COLS + col` is set iff that cell is empty.
+    open: u128,
+    // Bit `row * COLS + col` is set iff that cell holds a value passed to `new`.
+    givens: u128,
+    // Bit `row * COLS + col` is set iff that cell holds a value passed to `set`.
+    entered: u128,
+}
+
+impl Sudoku {
+    /// Intialize a sudoku board.
+    /// Takes in an initial board state defined as a vector of tuples.
+    /// Tuples take the form of (row index, column index, value). Values should be an
+    /// integer between 1 and 9, and may not repeat within a row, column or cage.
+    pub fn new(initial: Vec<(usize, usize, u8)>) -> Result<Self, SudokuError> {
+        let sudoku = Self::from_values_(initial)?;
+        let conflicts = sudoku.conflicts();
+        if !conflicts.is_empty() {
+            return Err(SudokuError::ConflictingGivens(conflicts));
+        }
+        Ok(sudoku)
+    }
+
+    /// Intialize a sudoku board without checking the values against each other.
+    pub(crate) fn from_values_(initial: Vec<(usize, usize, u8)>) -> Result<Self, SudokuError> {
+        let mut board = Vec::with_capacity(ROWS);
+        for _ in 0..ROWS {
+            let mut row = Vec::with_capacity(COLS);
+            for _ in 0..COLS {
+                row.push(None);
+            }
+            board.push(row);
+        }
+        let mut sudoku = Self {
+            board,
+            rows: [Digits::none(); ROWS],
+            cols: [Digits::none(); COLS],
+            cages: [Digits::none(); ROWS],
+            open: (1 << (ROWS * COLS)) - 1,
+            givens: 0,
+            entered: 0,
+        };
+
+        for (row, col, val) in initial {
+            if row >= ROWS || col >= COLS {
+                return Err(SudokuError::OutOfBounds { row, col, val });
+            }
+            if val == 0 || val > 9 {
+                return Err(SudokuError::InvalidValue { row, col, val });
+            }
+            if sudoku.board[row][col].is_some() {
+                return Err(SudokuError::DuplicatePlacement { row, col, val });
+            }
+            sudoku.place_(row, col, val);
+            sudoku.givens |= 1 << (row * COLS + col);
+        }
+
+        Ok(sudoku)
+    }
+
+    /// Return the value at the given cell, or None if the cell is empty.
+    /// Panics if the row or column index is out of bounds.
+    pub fn cell(&self, row: usize, col: usize) -> Option<u8> {
+        self.board[row][col]
+    }
+
+    /// Return the value at the given cell, or None if the cell is empty.
+    /// Return an error if the position is out of range.
+    pub fn get(&self, row: usize, col: usize) -> Result<Option<u8>, SudokuError> {
+        if row >= ROWS || col >= COLS {
+            return Err(SudokuError::InvalidPosition { row, col });
+        }
+        Ok(self.board[row][col])
+    }
+
+    /// Return where the value at the given cell came from, or None if the cell is empty.
+    /// Panics if the row or column index is out of bounds.
+    pub fn origin(&self, row: usize, col: usize) -> Option<Origin> {
+        self.board[row][col]?;
+        let bit = 1 << (row * COLS + col);
+        if self.givens & bit != 0 {
+            Some(Origin::Given)
+        } else if self.entered & bit != 0 {
+            Some(Origin::Entered)
+        } else {
+            Some(Origin::Solved)
+        }
+    }
+
+    /// Return true iff the given cell holds a value passed to `new`.
+    /// Panics if the row or column index is out of bounds.
+    pub fn is_given(&self, row: usize, col: usize) -> bool {
+        self.origin(row, col) == Some(Origin::Given)
+    }
+
+    /// Place the given value in the given cell, replacing any value that is not a given.
+    /// The cell is then reported as `Origin::Entered`.
+    /// Return an error if the position or value is out of range, if the cell holds a
+    /// given, or if the value already appears in the same row, column or cage.
+    /// The board is left unchanged on error.
+    pub fn set(&mut self, row: usize, col: usize, val: u8) -> Result<(), SudokuError> {
+        self.set_with(row, col, val, Check::Reject)
+    }
+
+    /// Place the given value in the given cell like `set`.
+    /// With `Check::Allow`, the value is placed even if it repeats a value in the same
+    /// row, column or cage. A board holding such a repeat cannot be solved until the
+    /// offending value is changed or cleared.
+    pub fn set_with(
+        &mut self,
+        row: usize,
+        col: usize,
+        val: u8,
+        check: Check,
+    ) -> Result<(), SudokuError> {
+        if row >= ROWS || col >= COLS {
+            return Err(SudokuError::OutOfBounds { row, col, val });
+        }
+        self.check_editable_(row, col)?;
+        if val == 0 || val > 9 {
+            return Err(SudokuError::InvalidValue { row, col, val });
+        }
+        let previous = self.board[row][col];
+        self.erase_(row, col);
+        if check == Check::Reject {
+            if let Some(conflict) = self.conflict_at_(row, col, val) {
+                if let Some(previous) = previous {
+                    self.place_(row, col, previous);
+                }
+                return Err(SudokuError::ConflictingPlacement(conflict));
+            }
+        }
+        self.place_(row, col, val);
+        self.entered |= 1 << (row * COLS + col);
+        Ok(())
+    }
+
+    /// Remove the value from the given cell and return it, or None if the cell was empty.
+    /// Return an error if the position is out of range or if the cell holds a given.
+    pub fn clear(&mut self, row: usize, col: usize) -> Result<Option<u8>, SudokuError> {
+        self.check_editable_(row, col)?;
+        let previous = self.board[row][col];
+        self.erase_(row, col);
+        self.entered &= !(1 << (row * COLS + col));
+        Ok(previous)
+    }
+
+    /// Remove the value from the given cell like `remove_`, keeping the masks correct
+    /// even if the same value also appears elsewhere in one of the cell's units.
+    fn erase_(&mut self, row: usize, col: usize) {
+        self.remove_(row, col);
+        self.rows = [Digits::none(); ROWS];
+        self.cols = [Digits::none(); COLS];
+        self.cages = [Digits::none(); ROWS];
+        for row in 0..ROWS {
+            for col in 0..COLS {
+                if let Some(val) = self.board[row][col] {
+                    self.rows[row].insert(val);
+                    self.cols[col].insert(val);
+                    self.cages[Self::cage_index_(row, col)].insert(val);
+                }
+            }
+        }
+    }
+
+    /// Return an error unless the given cell lies on the board and does not hold a given.
+    fn check_editable_(&self, row: usize, col: usize) -> Result<(), SudokuError> {
+        if row >= ROWS || col >= COLS {
+            return Err(SudokuError::InvalidPosition { row, col });
+        }
+        if self.is_given(row, col) {
+            return Err(SudokuError::GivenCell { row, col });
+        }
+        Ok(())
+    }
+
+    /// Return an iterator over every cell on the board in row-major order.
+    /// Items take the form of (row index, column index, value).
+    pub fn cells(&self) -> Cells<'_> {
+        Cells {
+            sudoku: self,
+            index: 0,
+        }
+    }
+
+    /// Return the values that can be placed in the given cell.
+    /// A filled cell has no candidates.
+    pub fn candidates(&self, row: usize, col: usize) -> Digits {
+        if self.board[row][col].is_some() {
+            return Digits::none();
+        }
+        !(self.rows[row] | self.cols[col] | self.cages[Self::cage_index_(row, col)])
+    }
+
+    /// Return an iterator over the row and column indexes of every empty cell, in
+    /// row-major order.
+    pub fn open_cells(&self) -> impl Iterator<Item = (usize, usize)> {
+        let mut open = self.open;
+        std::iter::from_fn(move || {
+            if open == 0 {
+                return None;
+            }
+            let index = open.trailing_zeros() as usize;
+            open &= open - 1;
+            Some((index / COLS, index % COLS))
+        })
+    }
+
+    /// Solve the sudoku board with backtracking and return an Ok if successful.
+    /// The cell with the fewest candidates is filled first.
+    /// If the board cannot be solved, return `SudokuError::Unsolvable`.
+    /// This function mutates the internal board representation in-place.
+    pub fn solve(&mut self) -> Result<(), SudokuError> {
+        self.solve_with(&MinimumRemaining)
+    }
+
+    /// Return a solved copy of the board, leaving this board untouched.
+    /// Cells filled by the solver can be told apart from the givens with `origin`.
+    pub fn solved(&self) -> Result<Sudoku, SudokuError> {
+        let mut sudoku = self.clone();
+        sudoku.solve()?;
+        Ok(sudoku)
+    }
+
+    /// Solve the sudoku board with backtracking like `solve`, and return statistics
+    /// describing the work done.
+    pub fn solve_with_stats(&mut self) -> Result<SolveStats, SudokuError> {
+        self.solve_within(&Budget::unlimited())
+    }
+
+    /// Solve the sudoku board with backtracking like `solve`, giving up once the budget
+    /// runs out. If it does, return `SudokuError::BudgetExceeded` and leave the board
+    /// unchanged.
+    pub fn solve_within(&mut self, budget: &Budget) -> Result<SolveStats, SudokuError> {
+        let outcome = run_(budget, |tracker| {
+            self.solve_with_(&MinimumRemaining, tracker)
+        });
+        let stats = outcome.stats;
+        outcome.result.map(|_| stats)
+    }
+
+    /// Solve the sudoku board with backtracking, using the given strategy to choose
+    /// which empty cell to fill next.
+    pub fn solve_with(&mut self, selector: &dyn CellSelector) -> Result<(), SudokuError> {
+        run_(&Budget::unlimited(), |tracker| {
+            self.solve_with_(selector, tracker)
+        })
+        .result
+    }
+
+    /// Solve the sudoku board with backtracking, recording the work done in `tracker`.
+    pub(crate) fn solve_with_(
+        &mut self,
+        selector: &dyn CellSelector,
+        tracker: &mut Tracker,
+    ) -> Result<(), SudokuError> {
+        if self.consistent_() && self.search_(selector, 0, tracker, &mut |_| true) {
+            Ok(())
+        } else {
+            Err(SudokuError::Unsolvable)
+        }
+    }
+
+    /// Solve the sudoku board using the given method.
+    /// This function mutates the internal board representation in-place.
+    pub fn solve_using(&mut self, method: Method) -> Result<(), SudokuError> {
+        method.solver().solve(self).result
+    }
+
+    /// Count the solutions of the board, stopping once `limit` solutions have been found.
+    /// The board itself is left unchanged.
+    pub fn count_solutions(&self, limit: usize) -> SolutionCount {
+        if limit == 0 {
+            return SolutionCount::AtLeast(0);
+        }
+        if !self.consistent_() {
+            return SolutionCount::Exact(0);
+        }
+        let mut count = 0;
+        let budget = Budget::unlimited();
+        let mut tracker = Tracker::new(&budget);
+        let reached = self
+            .clone()
+            .search_(&MinimumRemaining, 0, &mut tracker, &mut |_| {
+                count += 1;
+                count >= limit
+            });
+        if reached {
+            SolutionCount::AtLeast(count)
+        } else {
+            SolutionCount::Exact(count)
+        }
+    }
+
+    /// Return true iff the board has exactly one solution.
+    pub fn has_unique_solution(&self) -> bool {
+        self.count_solutions(2) == SolutionCount::Exact(1)
+    }
+
+    /// Run the backtracking search, calling `visit` on every complete board found.
+    /// Return true as soon as `visit` returns true, leaving the board in that solved state.
+    /// Otherwise every placement is undone and false is returned, including when the
+    /// budget of `tracker` runs out.
+    fn search_(
+        &mut self,
+        selector: &dyn CellSelector,
+        depth: usize,
+        tracker: &mut Tracker,
+        visit: &mut dyn FnMut(&Sudoku) -> bool,
+    ) -> bool {
+        if !tracker.enter_(depth) {
+            return false;
+        }
+        let (row, col) = match selector.select(self) {
+            Some(cell) => cell,
+            None => return visit(self),
+        };
+        let candidates = self.candidates(row, col);
+        for val in candidates {
+            self.place_(row, col, val);
+            tracker.stats.placements += 1;
+            if candidates.len() > 1 {
+                tracker.stats.guesses += 1;
+            }
+            if self.search_(selector, depth + 1, tracker, visit) {
+                return true;
+            }
+            self.remove_(row, col);
+            tracker.stats.backtracks += 1;
+            if tracker.exceeded_() {
+                return false;
+            }
+        }
+        false
+    }
+
+    /// Return an iterator over every solution of the board.
+    /// Solutions are produced lazily, one search step at a time, so the board may be
+    /// empty or heavily under-constrained.
+    pub fn solutions(&self) -> Solutions {
+        Solutions::new(self)
+    }
+
+    /// Return true iff the board is complete and correct.
+    pub fn verify(&self) -> bool {
+        for i in 0..ROWS {
+            if !self.verify_row_(i) {
+                return false;
+            }
+        }
+        for j in 0..COLS {
+            if !self.verify_col_(j) {
+                return false;
+            }
+        }
+        for ci in 0..CAGE_ROWS {
+            for cj in 0..CAGE_COLS {
+                if !self.verify_cage_(ci, cj) {
+                    return false;
+                }
+            }
+        }
+        true
+    }
+
+    /// Return true iff the given row on the board is complete and correct.
+    fn verify_row_(&self, row: usize) -> bool {
+        let mut seen = [false; 10];
+        for col in 0..COLS {
+            let val = match self.board[row][col] {
+                Some(val) => val as usize,
+                None => return false,
+            };
+            if seen[val] || val > 9 {
+                return false;
+            }
+            seen[val] = true;
+        }
+        true
+    }
+
+    /// Return true iff the given column on the board is complete and correct.
+    fn verify_col_(&self, col: usize) -> bool {
+        let mut seen = [false; 10];
+        for row in 0..ROWS {
+            let val = match self.board[row][col] {
+                Some(val) => val as usize,
+                None => return false,
+            };
+            if seen[val] || val > 9 {
+                return false;
+            }
+            seen[val] = true;
+        }
+        true
+    }
+
+    /// Return true iff the given cage on the board is complete and correct.
+    /// A cage refers to a 3-by-3 square on the board with the sudoku constraint.
+    fn verify_cage_(&self, cage_row: usize, cage_col: usize) -> bool {
+        let mut seen = [false; 10];
+        for i in 0..CAGE_ROWS {
+            for j in 0..CAGE_COLS {
+                let val = match self.board[cage_row * CAGE_ROWS + i][cage_col * CAGE_COLS + j] {
+                    Some(val) => val as usize,
+                    None => return false,
+                };
+                if seen[val] || val > 9 {
+                    return false;
+                }
+                seen[val] = true;
+            }
+        }
+        true
+    }
+
+    /// Return the row and column indexes for a cell that does not contain a value.
+    /// If all cells are filled, return None.
+    pub(crate) fn find_open_cell_(&self) -> Option<(usize, usize)> {
+        if self.open == 0 {
+            return None;
+        }
+        let index = self.open.trailing_zeros() as usize;
+        Some((index / COLS, index % COLS))
+    }
+
+    /// Place the given value in the given cell, which must be empty.
+    pub(crate) fn place_(&mut self, row: usize, col: usize, val: u8) {
+        self.board[row][col] = Some(val);
+        self.rows[row].insert(val);
+        self.cols[col].insert(val);
+        self.cages[Self::cage_index_(row, col)].insert(val);
+        self.open &= !(1 << (row * COLS + col));
+    }
+
+    /// Place the given value in the given cell, which must be empty, and mark it as
+    /// entered by the player like `set` does, without checking it against the board.
+    pub(crate) fn enter_(&mut self, row: usize, col: usize, val: u8) {
+        self.place_(row, col, val);
+        self.entered |= 1 << (row * COLS + col);
+    }
+
+    /// Remove the value from the given cell, if any.
+    pub(crate) fn remove_(&mut self, row: usize, col: usize) {
+        if let Some(val) = self.board[row][col].take() {
+            self.rows[row].remove(val);
+            self.cols[col].remove(val);
+            self.cages[Self::cage_index_(row, col)].remove(val);
+            self.open |= 1 << (row * COLS + col);
+        }
+    }
+
+    /// Return true iff no value is repeated within a row, column or cage.
+    /// Each unit holds as many distinct values as filled cells exactly when it has no
+    /// repeats, so it is enough to compare the totals.
+    pub(crate) fn consistent_(&self) -> bool {
+        let filled = ROWS * COLS - self.open.count_ones() as usize;
+        let distinct: usize = self
+            .rows
+            .iter()
+            .chain(self.cols.iter())
+            .chain(self.cages.iter())
+            .map(|digits| digits.len())
+            .sum();
+        distinct == 3 * filled
+    }
+
+    /// Return the values already placed in the given unit.
+    pub(crate) fn unit_digits_(&self, unit: Unit) -> Digits {
+        match unit {
+            Unit::Row(row) => self.rows[row],
+            Unit::Col(col) => self.cols[col],
+            Unit::Cage(cage_row, cage_col) => self.cages[cage_row * (COLS / CAGE_COLS) + cage_col],
+        }
+    }
+
+    /// Return the row-major index of the cage containing the given cell.
+    fn cage_index_(row: usize, col: usize) -> usize {
+        row / CAGE_ROWS * (COLS / CAGE_COLS) + col / CAGE_COLS
+    }
+
+    /// Return true iff the given value can be placed in the given cell.
+    pub fn valid_insert(&self, row: usize, col: usize, val: u8) -> bool {
+        self.board[row][col].is_none()
+            && self.valid_row_insert_(row, val)
+            && self.valid_col_insert_(col, val)
+            && self.valid_cage_insert_(row / CAGE_ROWS, col / CAGE_COLS, val)
+    }
+
+    /// Return true iff the given value can be placed in the given row.
+    fn valid_row_insert_(&self, row: usize, val: u8) -> bool {
+        !self.rows[row].contains(val)
+    }
+
+    /// Return true iff the given value can be placed in the given column.
+    fn valid_col_insert_(&self, col: usize, val: u8) -> bool {
+        !self.cols[col].contains(val)
+    }
+
+    /// Return true iff the given value can be placed in the given cage.
+    /// A cage refers to a 3-by-3 square on the board with the sudoku constraint.
+    fn valid_cage_insert_(&self, cage_row: usize, cage_col: usize, val: u8) -> bool {
+        !self.cages[cage_row * (COLS / CAGE_COLS) + cage_col].contains(val)
+    }
+
+    /// Return the row and column indexes of every cell involved in a conflict, in
+    /// row-major order.
+    pub fn conflicting_cells(&self) -> Vec<(usize, usize)> {
+        let mut cells: Vec<_> = self
+            .conflicts()
+            .into_iter()
+            .flat_map(|conflict| vec![conflict.first, conflict.second])
+            .collect();
+        cells.sort_unstable();
+        cells.dedup();
+        cells
+    }
+
+    /// Return the first conflict that placing the given value in the given cell would cause.
+    fn conflict_at_(&self, row: usize, col: usize, val: u8) -> Option<Conflict> {
+        let units = [
+            Unit::Row(row),
+            Unit::Col(col),
+            Unit::Cage(row / CAGE_ROWS, col / CAGE_COLS),
+        ];
+        for &unit in &units {
+            if !self.unit_digits_(unit).contains(val) {
+                continue;
+            }
+            let cells = unit.cells();
+            let other = cells
+                .iter()
+                .position(|&(r, c)| (r, c) != (row, col) && self.board[r][c] == Some(val));
+            if let Some(i) = other {
+                let here = cells.iter().position(|&cell| cell == (row, col)).unwrap();
+                let (first, second) = if i < here {
+                    (cells[i], (row, col))
+                } else {
+                    ((row, col), cells[i])
+                };
+                return Some(Conflict {
+                    unit,
+                    first,
+                    second,
+                    val,
+                });
+            }
+        }
+        None
+    }
+
+    /// Return every pair of cells that share a unit and hold the same value, ordered by
+    /// unit as in `Unit::all`. Empty cells never conflict, so this works on partially
+    /// filled boards. A pair sharing both a line and a cage is reported once per unit.
+    pub fn conflicts(&self) -> Vec<Conflict> {
+        let mut conflicts = Vec::new();
+        for unit in Unit::all() {
+            let cells = unit.cells();
+            for (i, &first) in cells.iter().enumerate() {
+                let val = match self.board[first.0][first.1] {
+                    Some(val) => val,
+                    None => continue,
+                };
+                for &second in &cells[i + 1..] {
+                    if self.board[second.0][second.1] == Some(val) {
+                        conflicts.push(Conflict {
+                            unit,
+                            first,
+                            second,
+                            val,
+                        });
+                    }
+                }
+            }
+        }
+        conflicts
+    }
+}
+
+/// Where the value in a cell came from.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Origin {
+    /// The value was part of the initial board passed to `Sudoku::new`.
+    Given,
+    /// The value was placed with `Sudoku::set`.
+    Entered,
+    /// The value was filled in by a solver.
+    Solved,
+}
+
+/// Whether `Sudoku::set_with` checks a value against the rest of the board.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Check {
+    /// Refuse values that repeat a value in the same row, column or cage.
+    Reject,
+    /// Place the value regardless, as a player might while solving by hand.
+    Allow,
+}
+
+/// Number of solutions found by `Sudoku::count_solutions`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SolutionCount {
+    /// The search was exhausted and found exactly this many solutions.
+    Exact(usize),
+    /// The search stopped at the limit, so there may be more solutions.
+    AtLeast(usize),
+}
+
+/// Iterator over the cells of a sudoku board, created by `Sudoku::cells`.
+pub struct Cells<'a> {
+    sudoku: &'a Sudoku,
+    index: usize,
+}
+
+impl<'a> Iterator for Cells<'a> {
+    type Item = (usize, usize, Option<u8>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.index >= ROWS * COLS {
+            return None;
+        }
+        let (row, col) = (self.index / COLS, self.index % COLS);
+        self.index += 1;
+        Some((row, col, self.sudoku.board[row][col]))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = ROWS * COLS - self.index;
+        (remaining, Some(remaining))
+    }
+}
+
+impl<'a> ExactSizeIterator for Cells<'a> {}
+
+impl Display for Sudoku {
+    /// Define how the board is formatted when printed.
+    /// The alternate flag (`{:#}`) draws a box grid with cage borders instead.
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> Result<(), Error> {
+        let style = if fmt.alternate() {
+            Style::Grid
+        } else {
+            Style::Spaced
+        };
+        self.render(style).fmt(fmt)
+    }
+}
+
+/// Unit tests.
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{boards, FirstOpen};
+
+    #[test]
+    fn create_puzzle() {
+        let puzzle = Sudoku::new(vec![(0, 1, 3), (5, 3, 8), (8, 8, 4)]);
+        assert!(puzzle.is_ok());
+    }
+
+    #[test]
+    fn create_invalid_puzzle() {
+        let puzzle = Sudoku::new(vec![(0, 0, 10)]);
+        assert!(puzzle.is_err());
+    }
+
+    #[test]
+    fn create_puzzle_errors() {
+        assert_eq!(
+            Sudoku::new(vec![(0, 9, 1)]),
+            Err(SudokuError::OutOfBounds {
+                row: 0,
+                col: 9,
+                val: 1
+            })
+        );
+        assert_eq!(
+            Sudoku::new(vec![(2, 3, 0)]),
+            Err(SudokuError::InvalidValue {
+                row: 2,
+                col: 3,
+                val: 0
+            })
+        );
+        assert_eq!(
+            Sudoku::new(vec![(4, 4, 1), (4, 4, 2)]),
+            Err(SudokuError::DuplicatePlacement {
+                row: 4,
+                col: 4,
+                val: 2
+            })
+        );
+    }
+
+    #[test]
+    fn create_conflicting_puzzle() {
+        assert_eq!(
+            Sudoku::new(boards::INVALID_COL.to_vec()),
+            Err(SudokuError::ConflictingGivens(vec![
+                Conflict {
+                    unit: Unit::Col(4),
+                    first: (0, 4),
+                    second: (1, 4),
+                    val: 2,
+                },
+                Conflict {
+                    unit: Unit::Cage(0, 1),
+                    first: (0, 4),
+                    second: (1, 4),
+                    val: 2,
+                },
+            ]))
+        );
+        assert_eq!(
+            Sudoku::new(boards::INVALID_CAGE.to_vec()),
+            Err(SudokuError::ConflictingGivens(vec![
+                Conflict {
+                    unit: Unit::Row(0),
+                    first: (0, 0),
+                    second: (0, 1),
+                    val: 2,
+                },
+                Conflict {
+                    unit: Unit::Cage(0, 0),
+                    first: (0, 0),
+                    second: (0, 1),
+                    val: 2,
+                },
+            ]))
+        );
+    }
+
+    #[test]
+    fn create_puzzle_reports_every_conflict() {
+        let puzzle = Sudoku::new(vec![(0, 0, 5), (0, 8, 5), (8, 0, 5), (1, 1, 5)]);
+        match puzzle {
+            Err(SudokuError::ConflictingGivens(conflicts)) => assert_eq!(conflicts.len(), 3),
+            other => panic!("expected conflicting givens, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn read_cells() {
+        let puzzle = Sudoku::new(vec![(0, 1, 3), (5, 3, 8), (8, 8, 4)]).unwrap();
+        assert_eq!(puzzle.cell(0, 1), Some(3));
+        assert_eq!(puzzle.cell(0, 0), None);
+        assert_eq!(puzzle.cells().len(), ROWS * COLS);
+        let filled: Vec<_> = puzzle
+            .cells()
+            .filter_map(|(row, col, val)| val.map(|v| (row, col, v)))
+            .collect();
+        assert_eq!(filled, vec![(0, 1, 3), (5, 3, 8), (8, 8, 4)]);
+    }
+
+    #[test]
+    fn verify_valid_solution() {
+        let puzzle = Sudoku::new(boards::VALID_SOLUTION.to_vec()).unwrap();
+        assert!(puzzle.verify());
+    }
+
+    #[test]
+    fn verify_invalid_solution() {
+        let puzzle = Sudoku::from_values_(boards::INVALID_SOLUTION.to_vec()).unwrap();
+        assert!(!puzzle.verify());
+    }
+
+    #[test]
+    fn verify_valid_row() {
+        let puzzle = Sudoku::new(boards::VALID_ROW.to_vec()).unwrap();
+        assert!(puzzle.verify_row_(4));
+    }
+
+    #[test]
+    fn verify_invalid_row() {
+        let puzzle = Sudoku::from_values_(boards::INVALID_ROW.to_vec()).unwrap();
+        assert!(!puzzle.verify_row_(4));
+    }
+
+    #[test]
+    fn verify_valid_col() {
+        let puzzle = Sudoku::new(boards::VALID_COL.to_vec()).unwrap();
+        assert!(puzzle.verify_col_(4));
+    }
+
+    #[test]
+    fn verify_invalid_col() {
+        let puzzle = Sudoku::from_values_(boards::INVALID_COL.to_vec()).unwrap();
+        assert!(!puzzle.verify_col_(4));
+    }
+
+    #[test]
+    fn verify_valid_cage() {
+        let puzzle = Sudoku::new(boards::VALID_CAGE.to_vec()).unwrap();
+        assert!(puzzle.verify_cage_(0, 0));
+    }
+
+    #[test]
+    fn verify_invalid_cage() {
+        let puzzle = Sudoku::from_values_(boards::INVALID_CAGE.to_vec()).unwrap();
+        assert!(!puzzle.verify_cage_(0, 0));
+    }
+
+    #[test]
+    fn try_valid_row_insert() {
+        let puzzle = Sudoku::new(Vec::new()).unwrap();
+        assert!(puzzle.valid_row_insert_(0, 1));
+    }
+
+    #[test]
+    fn try_invalid_row_insert() {
+        let puzzle = Sudoku::new(boards::VALID_ROW.to_vec()).unwrap();
+        assert!(!puzzle.valid_row_insert_(4, 1));
+    }
+
+    #[test]
+    fn try_valid_col_insert() {
+        let puzzle = Sudoku::new(Vec::new()).unwrap();
+        assert!(puzzle.valid_col_insert_(0, 1));
+    }
+
+    #[test]
+    fn try_invalid_col_insert() {
+        let puzzle = Sudoku::new(boards::VALID_COL.to_vec()).unwrap();
+        assert!(!puzzle.valid_col_insert_(4, 1));
+    }
+
+    #[test]
+    fn try_valid_cage_insert() {
+        let puzzle = Sudoku::new(Vec::new()).unwrap();
+        assert!(puzzle.valid_cage_insert_(0, 0, 1));
+    }
+
+    #[test]
+    fn try_invalid_cage_insert() {
+        let puzzle = Sudoku::new(boards::VALID_CAGE.to_vec()).unwrap();
+        assert!(!puzzle.valid_cage_insert_(0, 0, 1));
+    }
+
+    #[test]
+    fn place_and_remove_update_masks() {
+        let puzzle = Sudoku::new(boards::VALID_PUZZLE_1.to_vec()).unwrap();
+        let mut copy = puzzle.clone();
+        assert!(copy.valid_insert(0, 0, 2));
+        copy.place_(0, 0, 2);
+        assert!(!copy.valid_row_insert_(0, 2));
+        assert!(!copy.valid_col_insert_(0, 2));
+        assert!(!copy.valid_cage_insert_(0, 0, 2));
+        assert_eq!(copy.find_open_cell_(), Some((0, 2)));
+        copy.remove_(0, 0);
+        assert_eq!(copy, puzzle);
+    }
+
+    #[test]
+    fn solve_unsolvable_puzzle() {
+        let mut puzzle = Sudoku::new(boards::UNSOLVABLE_PUZZLE.to_vec()).unwrap();
+        assert_eq!(puzzle.solve(), Err(SudokuError::Unsolvable));
+    }
+
+    #[test]
+    fn count_unique_solution() {
+        let puzzle = Sudoku::new(boards::VALID_PUZZLE_1.to_vec()).unwrap();
+        assert_eq!(puzzle.count_solutions(10), SolutionCount::Exact(1));
+        assert!(puzzle.has_unique_solution());
+        assert_eq!(puzzle.cell(0, 0), None);
+    }
+
+    #[test]
+    fn count_multiple_solutions() {
+        let puzzle = Sudoku::new(Vec::new()).unwrap();
+        assert_eq!(puzzle.count_solutions(5), SolutionCount::AtLeast(5));
+        assert!(!puzzle.has_unique_solution());
+    }
+
+    #[test]
+    fn count_no_solutions() {
+        let puzzle = Sudoku::new(boards::UNSOLVABLE_PUZZLE.to_vec()).unwrap();
+        assert_eq!(puzzle.count_solutions(2), SolutionCount::Exact(0));
+        assert!(!puzzle.has_unique_solution());
+    }
+
+    #[test]
+    fn list_candidates() {
+        let puzzle = Sudoku::new(boards::VALID_PUZZLE_1.to_vec()).unwrap();
+        let candidates: Vec<_> = puzzle.candidates(0, 0).iter().collect();
+        assert_eq!(candidates, vec![2, 6, 8, 9]);
+        assert!(puzzle.candidates(0, 1).is_empty());
+        assert_eq!(puzzle.open_cells().count(), 81 - 22);
+        assert_eq!(puzzle.open_cells().next(), Some((0, 0)));
+    }
+
+    #[test]
+    fn solved_copy() {
+        let puzzle = Sudoku::new(boards::VALID_PUZZLE_1.to_vec()).unwrap();
+        let original = puzzle.clone();
+        let solution = puzzle.solved().unwrap();
+        assert_eq!(puzzle, original);
+        assert!(solution.verify());
+        assert_eq!(solution.origin(0, 1), Some(Origin::Given));
+        assert_eq!(solution.origin(0, 0), Some(Origin::Solved));
+        assert_eq!(puzzle.origin(0, 0), None);
+        let givens = solution
+            .cells()
+            .filter(|&(row, col, _)| solution.is_given(row, col))
+            .count();
+        assert_eq!(givens, 22);
+    }
+
+    #[test]
+    #[should_panic]
+    fn is_given_out_of_bounds() {
+        let puzzle = Sudoku::new(boards::VALID_PUZZLE_1.to_vec()).unwrap();
+        puzzle.is_given(0, 9);
+    }
+
+    #[test]
+    fn solved_unsolvable_copy() {
+        let puzzle = Sudoku::new(boards::UNSOLVABLE_PUZZLE.to_vec()).unwrap();
+        assert_eq!(puzzle.solved(), Err(SudokuError::Unsolvable));
+    }
+
+    #[test]
+    fn set_and_clear_cells() {
+        let mut puzzle = Sudoku::new(boards::VALID_PUZZLE_1.to_vec()).unwrap();
+        puzzle.set(0, 0, 2).unwrap();
+        assert_eq!(puzzle.cell(0, 0), Some(2));
+        assert_eq!(puzzle.origin(0, 0), Some(Origin::Entered));
+        assert!(!puzzle.valid_insert(0, 4, 2));
+        puzzle.set(0, 0, 6).unwrap();
+        assert!(puzzle.valid_insert(0, 4, 2));
+        assert_eq!(puzzle.clear(0, 0), Ok(Some(6)));
+        assert_eq!(puzzle.clear(0, 0), Ok(None));
+        assert_eq!(puzzle.origin(0, 0), None);
+        assert_eq!(
+            puzzle,
+            Sudoku::new(boards::VALID_PUZZLE_1.to_vec()).unwrap()
+        );
+    }
+
+    #[test]
+    fn set_and_clear_errors() {
+        let mut puzzle = Sudoku::new(boards::VALID_PUZZLE_1.to_vec()).unwrap();
+        let original = puzzle.clone();
+        assert_eq!(
+            puzzle.set(0, 1, 4),
+            Err(SudokuError::GivenCell { row: 0, col: 1 })
+        );
+        assert_eq!(
+            puzzle.clear(0, 1),
+            Err(SudokuError::GivenCell { row: 0, col: 1 })
+        );
+        assert_eq!(
+            puzzle.set(9, 0, 4),
+            Err(SudokuError::OutOfBounds {
+                row: 9,
+                col: 0,
+                val: 4
+            })
+        );
+        assert_eq!(
+            puzzle.clear(9, 0),
+            Err(SudokuError::InvalidPosition { row: 9, col: 0 })
+        );
+        assert_eq!(
+            puzzle.set(0, 0, 10),
+            Err(SudokuError::InvalidValue {
+                row: 0,
+                col: 0,
+                val: 10
+            })
+        );
+        let val = puzzle.cell(0, 1).unwrap();
+        assert_eq!(
+            puzzle.set(0, 0, val),
+            Err(SudokuError::ConflictingPlacement(Conflict {
+                unit: Unit::Row(0),
+                first: (0, 0),
+                second: (0, 1),
+                val,
+            }))
+        );
+        assert_eq!(puzzle, original);
+    }
+
+    #[test]
+    fn get_cells() {
+        let puzzle = Sudoku::new(boards::VALID_PUZZLE_1.to_vec()).unwrap();
+        assert_eq!(puzzle.get(0, 0), Ok(None));
+        assert_eq!(puzzle.get(0, 1), Ok(Some(1)));
+        assert_eq!(
+            puzzle.get(0, 9),
+            Err(SudokuError::InvalidPosition { row: 0, col: 9 })
+        );
+    }
+
+    #[test]
+    fn set_allowing_conflicts() {
+        let mut puzzle = Sudoku::new(boards::VALID_PUZZLE_1.to_vec()).unwrap();
+        puzzle.set_with(0, 0, 1, Check::Allow).unwrap();
+        puzzle.set_with(0, 2, 1, Check::Allow).unwrap();
+        assert_eq!(puzzle.get(0, 0), Ok(Some(1)));
+        assert!(!puzzle.consistent_());
+        assert!(puzzle.solved().is_err());
+        assert_eq!(puzzle.count_solutions(1), SolutionCount::Exact(0));
+        for method in Method::all().iter() {
+            assert_eq!(
+                puzzle.clone().solve_using(*method),
+                Err(SudokuError::Unsolvable)
+            );
+        }
+
+        puzzle.clear(0, 0).unwrap();
+        assert!(!puzzle.consistent_());
+        assert!(!puzzle.valid_insert(0, 0, 1));
+        puzzle.clear(0, 2).unwrap();
+        assert!(puzzle.consistent_());
+        assert!(!puzzle.valid_insert(0, 0, 1));
+        assert_eq!(
+            puzzle,
+            Sudoku::new(boards::VALID_PUZZLE_1.to_vec()).unwrap()
+        );
+    }
+
+    #[test]
+    fn conflicts_on_partial_board() {
+        let mut puzzle = Sudoku::new(boards::VALID_PUZZLE_1.to_vec()).unwrap();
+        assert!(puzzle.conflicts().is_empty());
+        assert!(puzzle.conflicting_cells().is_empty());
+        puzzle.set_with(0, 0, 1, Check::Allow).unwrap();
+        puzzle.set_with(4, 0, 7, Check::Allow).unwrap();
+        assert_eq!(
+            puzzle.conflicts(),
+            vec![
+                Conflict {
+                    unit: Unit::Row(0),
+                    first: (0, 0),
+                    second: (0, 1),
+                    val: 1,
+                },
+                Conflict {
+                    unit: Unit::Col(0),
+                    first: (4, 0),
+                    second: (5, 0),
+                    val: 7,
+                },
+                Conflict {
+                    unit: Unit::Cage(0, 0),
+                    first: (0, 0),
+                    second: (0, 1),
+                    val: 1,
+                },
+                Conflict {
+                    unit: Unit::Cage(1, 0),
+                    first: (4, 0),
+                    second: (5, 0),
+                    val: 7,
+                },
+            ]
+        );
+        assert_eq!(
+            puzzle.conflicting_cells(),
+            vec![(0, 0), (0, 1), (4, 0), (5, 0)]
+        );
+    }
+
+    #[test]
+    fn solve_keeps_entered_cells() {
+        let mut puzzle = Sudoku::new(boards::VALID_PUZZLE_1.to_vec()).unwrap();
+        let solution = puzzle.solved().unwrap();
+        let (row, col, val) = solution
+            .cells()
+            .find(|&(row, col, _)| !solution.is_given(row, col))
+            .map(|(row, col, val)| (row, col, val.unwrap()))
+            .unwrap();
+        puzzle.set(row, col, val).unwrap();
+        puzzle.solve().unwrap();
+        assert_eq!(puzzle.origin(row, col), Some(Origin::Entered));
+        assert_eq!(puzzle.cells().count(), 81);
+        assert!(puzzle.verify());
+    }
+
+    #[test]
+    fn solve_with_stats() {
+        let mut puzzle = Sudoku::new(boards::VALID_PUZZLE_1.to_vec()).unwrap();
+        let stats = puzzle.solve_with_stats().unwrap();
+        assert!(puzzle.verify());
+        assert_eq!(stats.placements - stats.backtracks, 81 - 22);
+        assert_eq!(stats.max_depth, 81 - 22);
+        assert!(stats.nodes > stats.max_depth as u64);
+    }
+
+    #[test]
+    fn solve_with_first_open() {
+        let mut puzzle = Sudoku::new(boards::VALID_PUZZLE_1.to_vec()).unwrap();
+        let mut expected = puzzle.clone();
+        expected.solve().unwrap();
+        puzzle.solve_with(&FirstOpen).unwrap();
+        assert_eq!(puzzle, expected);
+    }
+
+    #[test]
+    fn solve_valid_puzzle_1() {
+        let mut puzzle = Sudoku::new(boards::VALID_PUZZLE_1.to_vec()).unwrap();
+        assert!(!puzzle.verify());
+        let _ = puzzle.solve();
+        assert!(puzzle.verify());
+    }
+
+    #[test]
+    fn solve_valid_puzzle_2() {
+        let mut puzzle = Sudoku::new(boards::VALID_PUZZLE_2.to_vec()).unwrap();
+        assert!(!puzzle.verify());
+        let _ = puzzle.solve();
+        assert!(puzzle.verify());
+    }
+
+    #[test]
+    fn solve_valid_puzzle_3() {
+        let mut puzzle = Sudoku::new(boards::VALID_PUZZLE_3.to_vec()).unwrap();
+        assert!(!puzzle.verify());
+        let _ = puzzle.solve();
+        assert!(puzzle.verify());
+    }
+}
@@ -0,0 +1,69 @@
+use crate::{CAGE_COLS, CAGE_ROWS, COLS, ROWS};
+
+/// A group of nine cells that must not contain the same value twice.
+/// A cage refers to a 3-by-3 square on the board, indexed by cage row and cage column.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Unit {
+    Row(usize),
+    Col(usize),
+    Cage(usize, usize),
+}
+
+impl Unit {
+    /// Return every unit on the board: all rows, then all columns, then all cages.
+    pub fn all() -> impl Iterator<Item = Unit> {
+        let rows = (0..ROWS).map(Unit::Row);
+        let cols = (0..COLS).map(Unit::Col);
+        let cages = (0..(ROWS / CAGE_ROWS) * (COLS / CAGE_COLS))
+            .map(|i| Unit::Cage(i / (COLS / CAGE_COLS), i % (COLS / CAGE_COLS)));
+        rows.chain(cols).chain(cages)
+    }
+
+    /// Return the row and column indexes of the cells in this unit.
+    pub fn cells(self) -> [(usize, usize); 9] {
+        let mut cells = [(0, 0); 9];
+        for (i, cell) in cells.iter_mut().enumerate() {
+            *cell = match self {
+                Unit::Row(row) => (row, i),
+                Unit::Col(col) => (i, col),
+                Unit::Cage(cage_row, cage_col) => (
+                    cage_row * CAGE_ROWS + i / CAGE_COLS,
+                    cage_col * CAGE_COLS + i % CAGE_COLS,
+                ),
+            };
+        }
+        cells
+    }
+}
+
+/// Two cells in the same unit that hold the same value.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Conflict {
+    pub unit: Unit,
+    pub first: (usize, usize),
+    pub second: (usize, usize),
+    pub val: u8,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn all_units() {
+        let units: Vec<_> = Unit::all().collect();
+        assert_eq!(units.len(), 27);
+        assert_eq!(units[0], Unit::Row(0));
+        assert_eq!(units[9], Unit::Col(0));
+        assert_eq!(units[18], Unit::Cage(0, 0));
+        assert_eq!(units[26], Unit::Cage(2, 2));
+    }
+
+    #[test]
+    fn cage_cells() {
+        let cells = Unit::Cage(1, 2).cells();
+        assert_eq!(cells[0], (3, 6));
+        assert_eq!(cells[4], (4, 7));
+        assert_eq!(cells[8], (5, 8));
+    }
+}